@@ -30,6 +30,10 @@ pub use instance::*;
 mod call;
 pub use call::*;
 
+pub mod proxy;
+
+pub mod storage;
+
 // Not public API.
 // NOTE: please avoid changing the API of this module due to its use in the `sol!` macro.
 #[doc(hidden)]
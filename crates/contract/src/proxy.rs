@@ -0,0 +1,181 @@
+//! Helpers for checking contract deployment and resolving proxy implementations.
+//!
+//! Proxies are detected through the [EIP-1167] minimal proxy bytecode and the [EIP-1967] storage
+//! slots, which together cover the proxy patterns used by OpenZeppelin and most factories.
+//!
+//! [EIP-1167]: https://eips.ethereum.org/EIPS/eip-1167
+//! [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{b256, bytes, hex, Address, Bytes, B256, U256};
+use alloy_provider::Provider;
+use alloy_transport::{Transport, TransportResult};
+
+/// The EIP-1967 storage slot holding the implementation address,
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The EIP-1967 storage slot holding the beacon address,
+/// `bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)`.
+pub const EIP1967_BEACON_SLOT: B256 =
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// The selector of the beacon's `implementation()` function.
+const IMPLEMENTATION_SELECTOR: Bytes = bytes!("5c60da1b");
+
+/// The runtime code of an EIP-1167 minimal proxy before the implementation address.
+const MINIMAL_PROXY_PREFIX: [u8; 10] = hex!("363d3d373d3d3d363d73");
+
+/// The runtime code of an EIP-1167 minimal proxy after the implementation address.
+const MINIMAL_PROXY_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// Returns `true` if there is code deployed at the given address.
+///
+/// Note that this returns `false` for a contract that is still being constructed, as well as for
+/// accounts whose code has been self-destructed.
+pub async fn is_contract<T, P, N>(provider: &P, address: Address) -> TransportResult<bool>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    Ok(!provider.get_code_at(address).await?.is_empty())
+}
+
+/// Returns the implementation address if the given runtime code is an EIP-1167 minimal proxy.
+pub fn minimal_proxy_implementation(code: &[u8]) -> Option<Address> {
+    let rest = code.strip_prefix(&MINIMAL_PROXY_PREFIX)?;
+    let implementation = rest.strip_suffix(&MINIMAL_PROXY_SUFFIX)?;
+    (implementation.len() == 20).then(|| Address::from_slice(implementation))
+}
+
+/// Resolves the implementation address of the proxy deployed at the given address.
+///
+/// The following proxy patterns are detected, in order:
+/// - EIP-1167 minimal proxies, from their runtime code.
+/// - EIP-1967 proxies, from the implementation slot.
+/// - EIP-1967 beacon proxies, by calling `implementation()` on the beacon.
+///
+/// Returns `None` if the address is not a recognized proxy.
+pub async fn resolve_proxy<T, P, N>(
+    provider: &P,
+    address: Address,
+) -> TransportResult<Option<Address>>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let code = provider.get_code_at(address).await?;
+    if let Some(implementation) = minimal_proxy_implementation(&code) {
+        return Ok(Some(implementation));
+    }
+
+    let slot = provider.get_storage_at(address, EIP1967_IMPLEMENTATION_SLOT.into()).await?;
+    if let Some(implementation) = word_to_address(slot) {
+        return Ok(Some(implementation));
+    }
+
+    let slot = provider.get_storage_at(address, EIP1967_BEACON_SLOT.into()).await?;
+    let Some(beacon) = word_to_address(slot) else { return Ok(None) };
+    let tx = N::TransactionRequest::default().with_to(beacon).with_input(IMPLEMENTATION_SELECTOR);
+    let output = provider.call(&tx).await?;
+    Ok(B256::try_from(output.as_ref()).ok().and_then(|word| word_to_address(word.into())))
+}
+
+/// Interprets a storage word as an address, returning `None` if it is zero.
+fn word_to_address(word: U256) -> Option<Address> {
+    (!word.is_zero()).then(|| Address::from_word(word.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+    use alloy_provider::{ext::AnvilApi, ProviderBuilder};
+
+    const IMPLEMENTATION: Address = address!("bebebebebebebebebebebebebebebebebebebebe");
+
+    #[test]
+    fn eip1967_slots() {
+        assert_eq!(
+            U256::from_be_bytes(keccak256("eip1967.proxy.implementation").0) - U256::from(1),
+            U256::from_be_bytes(EIP1967_IMPLEMENTATION_SLOT.0)
+        );
+        assert_eq!(
+            U256::from_be_bytes(keccak256("eip1967.proxy.beacon").0) - U256::from(1),
+            U256::from_be_bytes(EIP1967_BEACON_SLOT.0)
+        );
+    }
+
+    #[test]
+    fn minimal_proxy() {
+        let implementation = address!("bebebebebebebebebebebebebebebebebebebebe");
+        let code =
+            [&MINIMAL_PROXY_PREFIX[..], implementation.as_slice(), &MINIMAL_PROXY_SUFFIX[..]]
+                .concat();
+        assert_eq!(minimal_proxy_implementation(&code), Some(implementation));
+        assert_eq!(minimal_proxy_implementation(&code[1..]), None);
+        assert_eq!(minimal_proxy_implementation(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn resolve_eoa() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let eoa = address!("1111111111111111111111111111111111111111");
+
+        assert!(!is_contract(&provider, eoa).await.unwrap());
+        assert_eq!(resolve_proxy(&provider, eoa).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn resolve_minimal_proxy() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let proxy = address!("1111111111111111111111111111111111111111");
+        let code =
+            [&MINIMAL_PROXY_PREFIX[..], IMPLEMENTATION.as_slice(), &MINIMAL_PROXY_SUFFIX[..]]
+                .concat();
+        provider.anvil_set_code(proxy, code.into()).await.unwrap();
+
+        assert!(is_contract(&provider, proxy).await.unwrap());
+        assert_eq!(resolve_proxy(&provider, proxy).await.unwrap(), Some(IMPLEMENTATION));
+    }
+
+    #[tokio::test]
+    async fn resolve_eip1967_proxy() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let proxy = address!("1111111111111111111111111111111111111111");
+        provider.anvil_set_code(proxy, bytes!("00")).await.unwrap();
+        provider
+            .anvil_set_storage_at(
+                proxy,
+                EIP1967_IMPLEMENTATION_SLOT.into(),
+                IMPLEMENTATION.into_word(),
+            )
+            .await
+            .unwrap();
+
+        assert!(is_contract(&provider, proxy).await.unwrap());
+        assert_eq!(resolve_proxy(&provider, proxy).await.unwrap(), Some(IMPLEMENTATION));
+    }
+
+    #[tokio::test]
+    async fn resolve_beacon_proxy() {
+        let provider = ProviderBuilder::new().on_anvil();
+        // A beacon returning the implementation address from any call:
+        // PUSH20 <implementation> PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let beacon = address!("2222222222222222222222222222222222222222");
+        let code = [&hex!("73")[..], IMPLEMENTATION.as_slice(), &hex!("60005260206000f3")].concat();
+        provider.anvil_set_code(beacon, code.into()).await.unwrap();
+
+        let proxy = address!("1111111111111111111111111111111111111111");
+        provider.anvil_set_code(proxy, bytes!("00")).await.unwrap();
+        provider
+            .anvil_set_storage_at(proxy, EIP1967_BEACON_SLOT.into(), beacon.into_word())
+            .await
+            .unwrap();
+
+        assert_eq!(resolve_proxy(&provider, proxy).await.unwrap(), Some(IMPLEMENTATION));
+    }
+}
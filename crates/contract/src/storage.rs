@@ -0,0 +1,119 @@
+//! Helpers for locating and decoding Solidity state variables in contract storage.
+//!
+//! These follow the [Solidity storage layout] rules, and can be combined with
+//! [`Provider::get_storage_at`](alloy_provider::Provider::get_storage_at) to read contract state
+//! that is not exposed through a view function:
+//!
+//! ```no_run
+//! # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+//! use alloy_contract::storage;
+//! use alloy_primitives::{address, U256};
+//!
+//! let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+//! let holder = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! // `mapping(address => uint256) balances` declared at slot 9
+//! let slot = storage::mapping_slot(&holder, U256::from(9));
+//! let balance = provider.get_storage_at(token, slot).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Solidity storage layout]: https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html
+
+use alloy_primitives::{keccak256, U256};
+use alloy_sol_types::{SolType, SolValue};
+
+/// Returns the storage slot of the value for `key` in a mapping declared at `slot`.
+///
+/// Value type keys are padded to 32 bytes, while `string` and `bytes` keys are hashed unpadded,
+/// as done by the Solidity compiler. For nested mappings, pass the returned slot as the `slot` of
+/// the next lookup.
+pub fn mapping_slot<K: SolValue>(key: &K, slot: U256) -> U256 {
+    let mut preimage =
+        if <K::SolType as SolType>::DYNAMIC { key.abi_encode_packed() } else { key.abi_encode() };
+    preimage.extend_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage).into()
+}
+
+/// Returns the storage slot holding the element at `index` of a dynamic array declared at `slot`,
+/// together with the byte offset of the element within that slot.
+///
+/// `element_size` is the size of an element in bytes. Elements of up to 16 bytes are packed
+/// several to a slot; larger elements, such as structs, occupy a whole number of slots, so their
+/// size should be given as a multiple of 32. Fixed-size arrays are stored inline starting at their
+/// declared slot, and are not covered by this function.
+///
+/// # Panics
+///
+/// Panics if `element_size` is zero.
+pub fn array_element_slot(slot: U256, index: U256, element_size: usize) -> (U256, usize) {
+    assert!(element_size > 0, "element size must be non-zero");
+    let base = U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0);
+    if element_size > 16 {
+        let slots_per_element = U256::from(element_size.div_ceil(32));
+        (base.wrapping_add(index.wrapping_mul(slots_per_element)), 0)
+    } else {
+        let per_slot = U256::from(32 / element_size);
+        let offset = (index % per_slot).to::<usize>() * element_size;
+        (base.wrapping_add(index / per_slot), offset)
+    }
+}
+
+/// Extracts a value of `size` bytes packed at byte `offset` of a storage word.
+///
+/// Solidity packs variables sharing a slot starting from the lower-order bytes, so an offset of
+/// zero refers to the rightmost bytes of the word.
+///
+/// # Panics
+///
+/// Panics if the value does not fit in the word, i.e. `offset + size > 32`.
+pub fn unpack_slot(word: U256, offset: usize, size: usize) -> U256 {
+    assert!(offset + size <= 32, "packed value exceeds the storage word");
+    let value = word >> (offset * 8);
+    if size == 32 {
+        value
+    } else {
+        value & ((U256::from(1) << (size * 8)) - U256::from(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, B256};
+
+    #[test]
+    fn mapping_slots() {
+        let holder = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let slot = mapping_slot(&holder, U256::from(9));
+        let expected = keccak256(
+            [B256::left_padding_from(holder.as_slice()), B256::with_last_byte(9)].concat(),
+        );
+        assert_eq!(B256::from(slot), expected);
+
+        let slot = mapping_slot(&String::from("abc"), U256::from(1));
+        let expected = keccak256([b"abc".as_slice(), B256::with_last_byte(1).as_slice()].concat());
+        assert_eq!(B256::from(slot), expected);
+    }
+
+    #[test]
+    fn array_slots() {
+        let base = U256::from_be_bytes(
+            b256!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563").0,
+        );
+        assert_eq!(array_element_slot(U256::ZERO, U256::from(3), 32), (base + U256::from(3), 0));
+        assert_eq!(array_element_slot(U256::ZERO, U256::from(3), 64), (base + U256::from(6), 0));
+        // uint64[]: four elements per slot
+        assert_eq!(array_element_slot(U256::ZERO, U256::from(5), 8), (base + U256::from(1), 8));
+    }
+
+    #[test]
+    fn unpack() {
+        // uint128 a = 1; uint64 b = 2; uint32 c = 3;
+        let word = U256::from(1) | (U256::from(2) << 128) | (U256::from(3) << 192);
+        assert_eq!(unpack_slot(word, 0, 16), U256::from(1));
+        assert_eq!(unpack_slot(word, 16, 8), U256::from(2));
+        assert_eq!(unpack_slot(word, 24, 4), U256::from(3));
+        assert_eq!(unpack_slot(word, 0, 32), word);
+    }
+}
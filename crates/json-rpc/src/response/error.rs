@@ -67,6 +67,27 @@ impl<E> ErrorPayload<E> {
             }
         }
     }
+
+    /// Returns `true` if the error indicates that a transaction was rejected because its nonce
+    /// has already been used by the sender.
+    ///
+    /// Recovering from this usually requires refetching the account's transaction count.
+    pub fn is_nonce_too_low(&self) -> bool {
+        let msg = self.message.to_lowercase().replace('_', " ");
+        // geth, reth and besu use variations of `nonce too low`, nethermind `OldNonce`
+        msg.contains("nonce too low") || msg.contains("oldnonce")
+    }
+
+    /// Returns `true` if the error indicates that a transaction was rejected because it replaces a
+    /// pending transaction with the same nonce without paying a sufficiently higher fee.
+    ///
+    /// Recovering from this requires bumping the fees of the replacement transaction.
+    pub fn is_replacement_underpriced(&self) -> bool {
+        let msg = self.message.to_lowercase().replace('_', " ");
+        // geth, reth and besu use variations of `replacement transaction underpriced`, nethermind
+        // `ReplacementNotAllowed`
+        msg.contains("replacement transaction underpriced") || msg.contains("replacementnotallowed")
+    }
 }
 
 /// Recursively traverses the value, looking for hex data that it can extract.
@@ -330,4 +351,38 @@ mod test {
 
         assert_eq!(value.a, U256::from(1));
     }
+
+    #[test]
+    fn nonce_too_low() {
+        for json in [
+            // geth
+            r#"{"code":-32000,"message":"nonce too low: address 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266, tx: 0 state: 1"}"#,
+            // reth
+            r#"{"code":-32003,"message":"nonce too low: next nonce 1, tx nonce 0"}"#,
+            // besu
+            r#"{"code":-32001,"message":"Nonce too low"}"#,
+            // nethermind
+            r#"{"code":-32010,"message":"OldNonce, Current nonce: 1, nonce of rejected tx: 0"}"#,
+        ] {
+            let payload: ErrorPayload = serde_json::from_str(json).unwrap();
+            assert!(payload.is_nonce_too_low(), "{json}");
+            assert!(!payload.is_replacement_underpriced(), "{json}");
+        }
+    }
+
+    #[test]
+    fn replacement_underpriced() {
+        for json in [
+            // geth and reth
+            r#"{"code":-32000,"message":"replacement transaction underpriced"}"#,
+            // besu
+            r#"{"code":-32000,"message":"Replacement transaction underpriced"}"#,
+            // nethermind
+            r#"{"code":-32010,"message":"ReplacementNotAllowed"}"#,
+        ] {
+            let payload: ErrorPayload = serde_json::from_str(json).unwrap();
+            assert!(payload.is_replacement_underpriced(), "{json}");
+            assert!(!payload.is_nonce_too_low(), "{json}");
+        }
+    }
 }
@@ -365,6 +365,25 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         let client = ClientBuilder::default().hyper_http(url);
         self.on_client(client)
     }
+
+    /// Build this provider with a [`ReplayTransport`] serving the calls of the given JSON
+    /// recording. This is intended for tests that need responses anvil cannot produce.
+    ///
+    /// [`ReplayTransport`]: alloy_transport::ReplayTransport
+    #[cfg(test)]
+    pub(crate) fn on_replay(self, recording: &str) -> F::Provider
+    where
+        L: ProviderLayer<
+            RootProvider<alloy_transport::ReplayTransport, N>,
+            alloy_transport::ReplayTransport,
+            N,
+        >,
+        F: TxFiller<N> + ProviderLayer<L::Provider, alloy_transport::ReplayTransport, N>,
+        N: Network,
+    {
+        let recording = alloy_transport::layers::Recording::from_json(recording).unwrap();
+        self.on_client(RpcClient::new(alloy_transport::ReplayTransport::new(recording), true))
+    }
 }
 
 #[cfg(any(test, feature = "anvil-node"))]
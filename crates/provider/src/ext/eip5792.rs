@@ -0,0 +1,82 @@
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, U64};
+use alloy_rpc_types_eth::eip5792::{CallsStatus, Capabilities, SendCallsRequest};
+use alloy_transport::{Transport, TransportResult};
+use std::collections::HashMap;
+
+/// EIP-5792 Wallet Call API
+///
+/// This module provides support for the `wallet_` methods defined in EIP-5792, which allow
+/// sending batches of calls to wallets and smart accounts that support them.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait Eip5792Api<N, T>: Send + Sync {
+    /// Sends a batch of calls to the wallet, returning an identifier for the batch.
+    async fn send_calls(&self, request: SendCallsRequest) -> TransportResult<String>;
+
+    /// Returns the status of a batch of calls sent with [`send_calls`](Self::send_calls).
+    async fn get_calls_status(&self, id: String) -> TransportResult<CallsStatus>;
+
+    /// Returns the capabilities supported by the wallet for the given account, keyed by chain id.
+    async fn get_capabilities(
+        &self,
+        address: Address,
+    ) -> TransportResult<HashMap<U64, Capabilities>>;
+
+    /// Requests the wallet to show the status of a batch of calls to the user.
+    async fn show_calls_status(&self, id: String) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> Eip5792Api<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn send_calls(&self, request: SendCallsRequest) -> TransportResult<String> {
+        self.client().request("wallet_sendCalls", (request,)).await
+    }
+
+    async fn get_calls_status(&self, id: String) -> TransportResult<CallsStatus> {
+        self.client().request("wallet_getCallsStatus", (id,)).await
+    }
+
+    async fn get_capabilities(
+        &self,
+        address: Address,
+    ) -> TransportResult<HashMap<U64, Capabilities>> {
+        self.client().request("wallet_getCapabilities", (address,)).await
+    }
+
+    async fn show_calls_status(&self, id: String) -> TransportResult<()> {
+        self.client().request("wallet_showCallsStatus", (id,)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_rpc_types_eth::eip5792::CallStatus;
+
+    #[tokio::test]
+    async fn send_calls_and_get_status() {
+        let provider = ProviderBuilder::new().on_replay(
+            r#"[
+                {"method":"wallet_getCapabilities","params":["0x0000000000000000000000000000000000000000"],"response":{"jsonrpc":"2.0","id":0,"result":{"0x2105":{"paymasterService":{"supported":true}}}}},
+                {"method":"wallet_sendCalls","params":[{"version":"1.0","chainId":"0x2105","from":"0x0000000000000000000000000000000000000000","calls":[]}],"response":{"jsonrpc":"2.0","id":0,"result":"0xbatch"}},
+                {"method":"wallet_getCallsStatus","params":["0xbatch"],"response":{"jsonrpc":"2.0","id":0,"result":{"status":"PENDING"}}}
+            ]"#,
+        );
+
+        let capabilities = provider.get_capabilities(Address::ZERO).await.unwrap();
+        assert!(capabilities[&U64::from(0x2105)].contains_key("paymasterService"));
+
+        let id = provider.send_calls(SendCallsRequest::new(0x2105, Address::ZERO)).await.unwrap();
+        let status = provider.get_calls_status(id).await.unwrap();
+        assert_eq!(status.status, CallStatus::Pending);
+    }
+}
@@ -1,15 +1,17 @@
 use crate::Provider;
 use alloy_network::Network;
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, Bytes, U64};
 use alloy_rpc_types_eth::erc4337::{
-    SendUserOperation, SendUserOperationResponse, UserOperationGasEstimation, UserOperationReceipt,
+    PaymasterData, PaymasterStubData, SendUserOperation, SendUserOperationResponse,
+    UserOperationGasEstimation, UserOperationReceipt,
 };
 use alloy_transport::{Transport, TransportResult};
 
 /// ERC-4337 Account Abstraction API
 ///
 /// This module provides support for the `eth_sendUserOperation` RPC method
-/// as defined in ERC-4337.
+/// as defined in ERC-4337, and for the paymaster web service capability
+/// defined in ERC-7677.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait Erc4337Api<N, T>: Send + Sync {
@@ -41,6 +43,30 @@ pub trait Erc4337Api<N, T>: Send + Sync {
         user_op: SendUserOperation,
         entry_point: Address,
     ) -> TransportResult<UserOperationGasEstimation>;
+
+    /// Requests stub paymaster data from a paymaster service, as defined in ERC-7677.
+    ///
+    /// The returned data is meant to be applied to the unsigned user operation before gas
+    /// estimation. The `context` is paymaster specific, e.g. a sponsorship policy id.
+    async fn get_paymaster_stub_data(
+        &self,
+        user_op: SendUserOperation,
+        entry_point: Address,
+        chain_id: u64,
+        context: serde_json::Value,
+    ) -> TransportResult<PaymasterStubData>;
+
+    /// Requests the final paymaster data from a paymaster service, as defined in ERC-7677.
+    ///
+    /// This should be called once the gas values of the user operation are final, right before
+    /// signing it.
+    async fn get_paymaster_data(
+        &self,
+        user_op: SendUserOperation,
+        entry_point: Address,
+        chain_id: u64,
+        context: serde_json::Value,
+    ) -> TransportResult<PaymasterData>;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -93,4 +119,125 @@ where
             }
         }
     }
+
+    async fn get_paymaster_stub_data(
+        &self,
+        user_op: SendUserOperation,
+        entry_point: Address,
+        chain_id: u64,
+        context: serde_json::Value,
+    ) -> TransportResult<PaymasterStubData> {
+        let chain_id = U64::from(chain_id);
+        match user_op {
+            SendUserOperation::EntryPointV06(user_op) => {
+                self.client()
+                    .request("pm_getPaymasterStubData", (user_op, entry_point, chain_id, context))
+                    .await
+            }
+            SendUserOperation::EntryPointV07(packed_user_op) => {
+                self.client()
+                    .request(
+                        "pm_getPaymasterStubData",
+                        (packed_user_op, entry_point, chain_id, context),
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn get_paymaster_data(
+        &self,
+        user_op: SendUserOperation,
+        entry_point: Address,
+        chain_id: u64,
+        context: serde_json::Value,
+    ) -> TransportResult<PaymasterData> {
+        let chain_id = U64::from(chain_id);
+        match user_op {
+            SendUserOperation::EntryPointV06(user_op) => {
+                self.client()
+                    .request("pm_getPaymasterData", (user_op, entry_point, chain_id, context))
+                    .await
+            }
+            SendUserOperation::EntryPointV07(packed_user_op) => {
+                self.client()
+                    .request(
+                        "pm_getPaymasterData",
+                        (packed_user_op, entry_point, chain_id, context),
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_primitives::{address, U256};
+    use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+
+    #[tokio::test]
+    async fn paymaster_params() {
+        let user_op = PackedUserOperation {
+            sender: address!("0000000000000000000000000000000000000001"),
+            nonce: U256::ZERO,
+            factory: Address::ZERO,
+            factory_data: Bytes::new(),
+            call_data: Bytes::new(),
+            call_gas_limit: U256::ZERO,
+            verification_gas_limit: U256::ZERO,
+            pre_verification_gas: U256::ZERO,
+            max_fee_per_gas: U256::ZERO,
+            max_priority_fee_per_gas: U256::ZERO,
+            paymaster: Address::ZERO,
+            paymaster_verification_gas_limit: U256::ZERO,
+            paymaster_post_op_gas_limit: U256::ZERO,
+            paymaster_data: Bytes::new(),
+            signature: Bytes::new(),
+        };
+        let entry_point = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+        let context = serde_json::json!({ "policyId": "policy" });
+        // Params are `[userOp, entryPoint, chainId, context]`.
+        let params = serde_json::json!([user_op, entry_point, "0x2105", context]);
+        let recording = serde_json::json!([
+            {
+                "method": "pm_getPaymasterStubData",
+                "params": params,
+                "response": {
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "result": {
+                        "paymaster": "0x0000000000000000000000000000000000000002",
+                        "paymasterData": "0x",
+                        "isFinal": false
+                    }
+                }
+            },
+            {
+                "method": "pm_getPaymasterData",
+                "params": params,
+                "response": {
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "result": {
+                        "paymaster": "0x0000000000000000000000000000000000000002",
+                        "paymasterData": "0x1234"
+                    }
+                }
+            }
+        ]);
+        let provider = ProviderBuilder::new().on_replay(&recording.to_string());
+        let user_op = SendUserOperation::EntryPointV07(user_op);
+
+        let stub = provider
+            .get_paymaster_stub_data(user_op.clone(), entry_point, 0x2105, context.clone())
+            .await
+            .unwrap();
+        assert_eq!(stub.is_final, Some(false));
+        let data =
+            provider.get_paymaster_data(user_op, entry_point, 0x2105, context).await.unwrap();
+        assert_eq!(data.paymaster_data, Some(Bytes::from_static(&[0x12, 0x34])));
+    }
 }
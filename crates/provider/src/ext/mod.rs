@@ -44,3 +44,8 @@ pub use txpool::TxPoolApi;
 mod erc4337;
 #[cfg(feature = "erc4337-api")]
 pub use erc4337::Erc4337Api;
+
+#[cfg(feature = "eip5792-api")]
+mod eip5792;
+#[cfg(feature = "eip5792-api")]
+pub use eip5792::Eip5792Api;
@@ -6,11 +6,12 @@ use crate::{
     utils::Eip1559Estimation,
     Provider,
 };
+use alloy_chains::Chain;
 use alloy_json_rpc::RpcError;
 use alloy_network::{Network, TransactionBuilder};
 use alloy_network_primitives::{BlockResponse, HeaderResponse};
 use alloy_rpc_types_eth::BlockNumberOrTag;
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
 use futures::FutureExt;
 
 /// An enum over the different types of gas fillable.
@@ -41,10 +42,16 @@ pub enum GasFillable {
 ///   `gas_price` field if unset.
 /// - if `blob_sidecar` is set, it will process as a 4844 tx and populate the `gas_limit`,
 ///   `max_fee_per_gas`, `max_priority_fee_per_gas` and `max_fee_per_blob_gas` fields if unset.
+/// - if `chain_id` is set to a chain listed as not supporting EIP-1559 (see
+///   [`Chain::is_legacy`](alloy_chains::Chain::is_legacy)) and neither `max_fee_per_gas` nor
+///   `max_priority_fee_per_gas` is set, it will process as a legacy tx and populate the `gas_limit`
+///   and `gas_price` fields if unset. This list may lag behind chains that have since adopted
+///   EIP-1559, setting the EIP-1559 fees explicitly bypasses it.
 /// - Otherwise, it will process as a EIP-1559 tx and populate the `gas_limit`, `max_fee_per_gas`
 ///   and `max_priority_fee_per_gas` fields if unset.
-/// - If the network does not support EIP-1559, it will fallback to the legacy tx and populate the
-///   `gas_limit` and `gas_price` fields if unset.
+/// - If the network does not support EIP-1559, i.e. it has no base fee or does not implement
+///   `eth_feeHistory`, it will fallback to the legacy tx and populate the `gas_limit` and
+///   `gas_price` fields if unset.
 ///
 /// # Example
 ///
@@ -212,11 +219,16 @@ impl<N: Network> TxFiller<N> for GasFiller {
             self.prepare_legacy(provider, tx).await
         } else if tx.blob_sidecar().is_some() {
             self.prepare_4844(provider, tx).await
+        } else if tx.max_fee_per_gas().is_none()
+            && tx.max_priority_fee_per_gas().is_none()
+            && tx.chain_id().is_some_and(|id| Chain::from_id(id).is_legacy())
+        {
+            self.prepare_legacy(provider, tx).await
         } else {
             match self.prepare_1559(provider, tx).await {
                 // fallback to legacy
                 Ok(estimate) => Ok(estimate),
-                Err(RpcError::UnsupportedFeature(_)) => self.prepare_legacy(provider, tx).await,
+                Err(e) if is_eip1559_unsupported(&e) => self.prepare_legacy(provider, tx).await,
                 Err(e) => Err(e),
             }
         }
@@ -250,6 +262,17 @@ impl<N: Network> TxFiller<N> for GasFiller {
     }
 }
 
+/// Returns `true` if the error indicates that the network cannot estimate EIP-1559 fees, either
+/// because it has no base fee or because `eth_feeHistory` is not implemented.
+const fn is_eip1559_unsupported(err: &RpcError<TransportErrorKind>) -> bool {
+    match err {
+        RpcError::UnsupportedFeature(_) => true,
+        // method not found
+        RpcError::ErrorResp(payload) => payload.code == -32601,
+        _ => false,
+    }
+}
+
 #[cfg(feature = "reqwest")]
 #[cfg(test)]
 mod tests {
@@ -320,4 +343,55 @@ mod tests {
 
         assert_eq!(receipt.effective_gas_price, 2000000000);
     }
+
+    #[tokio::test]
+    async fn legacy_chain() {
+        // BSC is known not to support EIP-1559, so fee history is never queried.
+        let provider = ProviderBuilder::new().on_anvil_with_config(|anvil| anvil.chain_id(56));
+        let tx = TransactionRequest { chain_id: Some(56), gas: Some(21000), ..Default::default() };
+        let fillable = GasFiller.prepare(&provider, &tx).await.unwrap();
+
+        let gas_price = provider.get_gas_price().await.unwrap();
+        assert_eq!(fillable, GasFillable::Legacy { gas_limit: 21000, gas_price });
+    }
+
+    #[tokio::test]
+    async fn legacy_chain_with_eip1559_fees() {
+        // Fees set by the caller are kept, even on chains listed as legacy.
+        let provider = ProviderBuilder::new().on_anvil_with_config(|anvil| anvil.chain_id(56));
+        let tx = TransactionRequest {
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            chain_id: Some(56),
+            max_fee_per_gas: Some(2_000_000_000),
+            max_priority_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let fillable = GasFiller.prepare(&provider, &tx).await.unwrap();
+
+        assert_eq!(
+            fillable,
+            GasFillable::Eip1559 {
+                gas_limit: 21000,
+                estimate: Eip1559Estimation {
+                    max_fee_per_gas: 2_000_000_000,
+                    max_priority_fee_per_gas: 1_000_000_000
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fee_history_unsupported() {
+        let provider = ProviderBuilder::new().on_replay(
+            r#"[
+                {"method":"eth_feeHistory","params":["0xa","latest",[20.0]],"response":{"jsonrpc":"2.0","id":0,"error":{"code":-32601,"message":"the method eth_feeHistory does not exist"}}},
+                {"method":"eth_gasPrice","response":{"jsonrpc":"2.0","id":0,"result":"0x3b9aca00"}}
+            ]"#,
+        );
+        let tx =
+            TransactionRequest { chain_id: Some(1337), gas: Some(21000), ..Default::default() };
+        let fillable = GasFiller.prepare(&provider, &tx).await.unwrap();
+
+        assert_eq!(fillable, GasFillable::Legacy { gas_limit: 21000, gas_price: 1_000_000_000 });
+    }
 }
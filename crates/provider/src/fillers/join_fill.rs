@@ -100,6 +100,11 @@ where
         self.right.fill_sync(tx);
     }
 
+    fn on_nonce_too_low(&self, tx: &N::TransactionRequest, nonce: u64) -> bool {
+        // both fillers are notified
+        self.left.on_nonce_too_low(tx, nonce) | self.right.on_nonce_too_low(tx, nonce)
+    }
+
     async fn prepare<P, T>(
         &self,
         provider: &P,
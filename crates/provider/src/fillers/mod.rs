@@ -13,7 +13,7 @@ mod wallet;
 pub use wallet::WalletFiller;
 
 mod nonce;
-pub use nonce::{CachedNonceManager, NonceFiller, NonceManager, SimpleNonceManager};
+pub use nonce::{CachedNonceManager, NonceFiller, NonceLease, NonceManager, SimpleNonceManager};
 
 mod gas;
 pub use gas::{GasFillable, GasFiller};
@@ -27,7 +27,7 @@ use crate::{
     RootProvider,
 };
 use alloy_json_rpc::RpcError;
-use alloy_network::{Ethereum, Network};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
 use alloy_transport::{Transport, TransportResult};
 use async_trait::async_trait;
 use futures_utils_wasm::impl_future;
@@ -183,6 +183,16 @@ pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug
         tx: SendableTx<N>,
     ) -> impl_future!(<Output = TransportResult<SendableTx<N>>>);
 
+    /// Called when a transaction filled by this filler was rejected because its nonce is too low,
+    /// with the request as it was before being filled and the rejected nonce.
+    ///
+    /// Fillers caching nonces should discard the cached nonce of the sender, unless a newer nonce
+    /// was handed out in the meantime, and return `true`, in which case the request is filled and
+    /// sent again. The default implementation returns `false`.
+    fn on_nonce_too_low(&self, _tx: &N::TransactionRequest, _nonce: u64) -> bool {
+        false
+    }
+
     /// Prepares and fills the transaction request with the fillable properties.
     fn prepare_and_fill<P, T>(
         &self,
@@ -206,6 +216,10 @@ pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug
     }
 }
 
+/// The number of times a transaction rejected because its nonce is too low is filled and sent
+/// again, see [`TxFiller::on_nonce_too_low`].
+const NONCE_TOO_LOW_RETRIES: usize = 3;
+
 /// A [`Provider`] that applies one or more [`TxFiller`]s.
 ///
 /// Fills arbitrary properties in a transaction request by composing multiple
@@ -213,6 +227,11 @@ pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug
 /// stack, and this is enforced when using [`ProviderBuilder::filler`] to
 /// construct this layer.
 ///
+/// If sending a filled transaction fails because its nonce is too low, e.g.
+/// because transactions were sent for the same account from elsewhere, the
+/// fillers are given a chance to resync through [`TxFiller::on_nonce_too_low`]
+/// and the transaction is filled and sent again, up to a few times.
+///
 /// Users should NOT use this struct directly. Instead, use
 /// [`ProviderBuilder::filler`] to construct and apply it to a stack.
 ///
@@ -250,12 +269,26 @@ where
         self.filler.join_with(other).layer(self.inner)
     }
 
-    async fn fill_inner(&self, mut tx: SendableTx<N>) -> TransportResult<SendableTx<N>> {
+    async fn fill_inner(&self, tx: SendableTx<N>) -> TransportResult<SendableTx<N>> {
+        self.fill_tracking_nonce(tx, &mut None).await
+    }
+
+    /// Fills the transaction request, keeping track of its nonce as last seen before it was
+    /// signed.
+    async fn fill_tracking_nonce(
+        &self,
+        mut tx: SendableTx<N>,
+        nonce: &mut Option<u64>,
+    ) -> TransportResult<SendableTx<N>> {
         let mut count = 0;
+        *nonce = tx.as_builder().and_then(|builder| builder.nonce());
 
         while self.filler.continue_filling(&tx) {
             self.filler.fill_sync(&mut tx);
             tx = self.filler.prepare_and_fill(&self.inner, tx).await?;
+            if let Some(builder) = tx.as_builder() {
+                *nonce = builder.nonce();
+            }
 
             count += 1;
             if count >= 20 {
@@ -274,6 +307,26 @@ where
     pub async fn fill(&self, tx: N::TransactionRequest) -> TransportResult<SendableTx<N>> {
         self.fill_inner(SendableTx::Builder(tx)).await
     }
+
+    async fn fill_and_send(
+        &self,
+        mut tx: SendableTx<N>,
+        nonce: &mut Option<u64>,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        tx = self.fill_tracking_nonce(tx, nonce).await?;
+
+        if let Some(builder) = tx.as_builder() {
+            if let FillerControlFlow::Missing(missing) = self.filler.status(builder) {
+                // TODO: improve this.
+                // blocked by #431
+                let message = format!("missing properties: {:?}", missing);
+                return Err(RpcError::local_usage_str(&message));
+            }
+        }
+
+        // Errors in tx building happen further down the stack.
+        self.inner.send_transaction_internal(tx).await
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -291,20 +344,29 @@ where
 
     async fn send_transaction_internal(
         &self,
-        mut tx: SendableTx<N>,
+        tx: SendableTx<N>,
     ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
-        tx = self.fill_inner(tx).await?;
+        let request = tx.as_builder().cloned();
+        let mut nonce = None;
+        let mut res = self.fill_and_send(tx, &mut nonce).await;
+
+        for _ in 0..NONCE_TOO_LOW_RETRIES {
+            let (Some(request), Some(rejected)) = (&request, nonce) else { break };
+            match &res {
+                Err(RpcError::ErrorResp(err)) if err.is_nonce_too_low() => {}
+                _ => break,
+            }
 
-        if let Some(builder) = tx.as_builder() {
-            if let FillerControlFlow::Missing(missing) = self.filler.status(builder) {
-                // TODO: improve this.
-                // blocked by #431
-                let message = format!("missing properties: {:?}", missing);
-                return Err(RpcError::local_usage_str(&message));
+            let mut tx = SendableTx::Builder(request.clone());
+            // e.g. the sender is filled synchronously by the wallet filler
+            self.filler.fill_sync(&mut tx);
+            let Some(builder) = tx.as_builder() else { break };
+            if !self.filler.on_nonce_too_low(builder, rejected) {
+                break;
             }
+            res = self.fill_and_send(tx, &mut nonce).await;
         }
 
-        // Errors in tx building happen further down the stack.
-        self.inner.send_transaction_internal(tx).await
+        res
     }
 }
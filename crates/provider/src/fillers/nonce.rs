@@ -3,6 +3,7 @@ use crate::{
     provider::SendableTx,
     Provider,
 };
+use alloy_eips::BlockId;
 use alloy_network::{Network, TransactionBuilder};
 use alloy_primitives::Address;
 use alloy_transport::{Transport, TransportResult};
@@ -21,6 +22,15 @@ pub trait NonceManager: Clone + Send + Sync + std::fmt::Debug {
         P: Provider<T, N>,
         N: Network,
         T: Transport + Clone;
+
+    /// Discards any nonce cached for the given account, after a transaction from it was rejected
+    /// because its `nonce` is too low.
+    ///
+    /// Returns `true` if the next nonce may differ from the rejected one, in which case the
+    /// transaction is filled and sent again. The default implementation returns `false`.
+    fn resync(&self, _address: Address, _nonce: u64) -> bool {
+        false
+    }
 }
 
 /// This [`NonceManager`] implementation will fetch the transaction count for any new account it
@@ -29,9 +39,28 @@ pub trait NonceManager: Clone + Send + Sync + std::fmt::Debug {
 /// Unlike [`CachedNonceManager`], this implementation does not store the transaction count locally,
 /// which results in more frequent calls to the provider, but it is more resilient to chain
 /// reorganizations.
+///
+/// The transaction count is fetched at the `latest` block by default. Use
+/// [`SimpleNonceManager::with_block_id`] to query the `pending` block instead on providers that
+/// expose their mempool.
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
-pub struct SimpleNonceManager;
+pub struct SimpleNonceManager {
+    block_id: BlockId,
+}
+
+impl SimpleNonceManager {
+    /// Sets the block at which the transaction count is fetched.
+    pub const fn with_block_id(mut self, block_id: BlockId) -> Self {
+        self.block_id = block_id;
+        self
+    }
+
+    /// Returns the block at which the transaction count is fetched.
+    pub const fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+}
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -42,7 +71,7 @@ impl NonceManager for SimpleNonceManager {
         N: Network,
         T: Transport + Clone,
     {
-        provider.get_transaction_count(address).await
+        provider.get_transaction_count(address).block_id(self.block_id).await
     }
 }
 
@@ -54,9 +83,138 @@ impl NonceManager for SimpleNonceManager {
 ///
 /// There is also an alternative implementation [`SimpleNonceManager`] that does not store the
 /// transaction count locally.
+///
+/// Clones of a `CachedNonceManager` share the same nonce cache. Transactions built outside the
+/// filler pipeline can obtain a nonce through [`CachedNonceManager::reserve_nonce`] on a clone of
+/// the manager used by the [`NonceFiller`], without colliding with filled transactions.
+///
+/// Like [`SimpleNonceManager`], the initial transaction count is fetched at the `latest` block
+/// unless configured otherwise with [`CachedNonceManager::with_block_id`].
 #[derive(Clone, Debug, Default)]
 pub struct CachedNonceManager {
-    nonces: DashMap<Address, Arc<Mutex<u64>>>,
+    nonces: Arc<DashMap<Address, Arc<Mutex<u64>>>>,
+    block_id: BlockId,
+}
+
+/// Use `u64::MAX` as a sentinel value to indicate that the nonce has not been fetched yet.
+const NONE: u64 = u64::MAX;
+
+impl CachedNonceManager {
+    /// Sets the block at which the initial transaction count of an account is fetched.
+    pub const fn with_block_id(mut self, block_id: BlockId) -> Self {
+        self.block_id = block_id;
+        self
+    }
+
+    /// Returns the block at which the initial transaction count of an account is fetched.
+    pub const fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+
+    /// Returns the nonce slot of the given account, holding the last nonce handed out.
+    fn slot(&self, address: Address) -> Arc<Mutex<u64>> {
+        // Locks dashmap internally for a short duration to clone the `Arc`.
+        // We also don't want to hold the dashmap lock through the await point in the callers.
+        let rm = self.nonces.entry(address).or_insert_with(|| Arc::new(Mutex::new(NONE)));
+        Arc::clone(rm.value())
+    }
+
+    /// Discards the cached nonce of the given account, so that the next nonce is fetched from the
+    /// provider again.
+    ///
+    /// This is done by the [`NonceFiller`] when a transaction it filled is rejected because its
+    /// nonce is too low (see
+    /// [`ErrorPayload::is_nonce_too_low`](alloy_json_rpc::ErrorPayload::is_nonce_too_low)), e.g.
+    /// because transactions were sent for the same account from elsewhere, unless a later nonce
+    /// has been handed out since.
+    ///
+    /// Outstanding [`NonceLease`]s of the account can no longer be released afterwards.
+    pub fn reset(&self, address: Address) {
+        self.nonces.remove(&address);
+    }
+
+    /// Reserves the next nonce of the given account for a transaction that is not sent through
+    /// the [`NonceFiller`].
+    ///
+    /// The reserved nonce will not be handed out again unless the returned [`NonceLease`] is
+    /// released.
+    pub async fn reserve_nonce<P, T, N>(
+        &self,
+        provider: &P,
+        address: Address,
+    ) -> TransportResult<NonceLease>
+    where
+        P: Provider<T, N>,
+        N: Network,
+        T: Transport + Clone,
+    {
+        let slot = self.slot(address);
+        let nonce = next_nonce(&slot, provider, address, self.block_id).await?;
+        Ok(NonceLease { nonce, slot, address, nonces: Arc::clone(&self.nonces) })
+    }
+}
+
+async fn next_nonce<P, T, N>(
+    slot: &Mutex<u64>,
+    provider: &P,
+    address: Address,
+    block_id: BlockId,
+) -> TransportResult<u64>
+where
+    P: Provider<T, N>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let mut nonce = slot.lock().await;
+    let new_nonce = if *nonce == NONE {
+        // Initialize the nonce if we haven't seen this account before.
+        provider.get_transaction_count(address).block_id(block_id).await?
+    } else {
+        *nonce + 1
+    };
+    *nonce = new_nonce;
+    Ok(new_nonce)
+}
+
+/// A nonce reserved with [`CachedNonceManager::reserve_nonce`].
+///
+/// Once a transaction using the nonce has been sent, the lease should be
+/// [committed](Self::commit). If the transaction is abandoned, the lease can be
+/// [released](Self::release) to make the nonce available again. Dropping the lease is
+/// equivalent to committing it.
+#[derive(Debug)]
+#[must_use = "the lease should be committed or released"]
+pub struct NonceLease {
+    nonce: u64,
+    slot: Arc<Mutex<u64>>,
+    address: Address,
+    nonces: Arc<DashMap<Address, Arc<Mutex<u64>>>>,
+}
+
+impl NonceLease {
+    /// Returns the reserved nonce.
+    pub const fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Commits the lease, marking the nonce as used.
+    pub fn commit(self) {}
+
+    /// Releases the lease, making the nonce available again.
+    ///
+    /// This only succeeds if no later nonce has been handed out for the same account in the
+    /// meantime, as releasing the nonce would otherwise leave a gap, and if the cached nonce has
+    /// not been [reset](CachedNonceManager::reset). Returns `true` if the nonce was released.
+    pub async fn release(self) -> bool {
+        let mut nonce = self.slot.lock().await;
+        let reset =
+            self.nonces.get(&self.address).map_or(true, |slot| !Arc::ptr_eq(&slot, &self.slot));
+        if *nonce != self.nonce || reset {
+            return false;
+        }
+        *nonce = self.nonce.checked_sub(1).unwrap_or(NONE);
+        true
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -68,25 +226,16 @@ impl NonceManager for CachedNonceManager {
         N: Network,
         T: Transport + Clone,
     {
-        // Use `u64::MAX` as a sentinel value to indicate that the nonce has not been fetched yet.
-        const NONE: u64 = u64::MAX;
-
-        // Locks dashmap internally for a short duration to clone the `Arc`.
-        // We also don't want to hold the dashmap lock through the await point below.
-        let nonce = {
-            let rm = self.nonces.entry(address).or_insert_with(|| Arc::new(Mutex::new(NONE)));
-            Arc::clone(rm.value())
-        };
+        next_nonce(&self.slot(address), provider, address, self.block_id).await
+    }
 
-        let mut nonce = nonce.lock().await;
-        let new_nonce = if *nonce == NONE {
-            // Initialize the nonce if we haven't seen this account before.
-            provider.get_transaction_count(address).await?
-        } else {
-            *nonce + 1
-        };
-        *nonce = new_nonce;
-        Ok(new_nonce)
+    /// Only discards the cached nonce if it still is the rejected one. If a later nonce has been
+    /// handed out in the meantime, e.g. to another transaction that was already resynced, it is
+    /// kept, as fetching the transaction count again would return that nonce a second time while
+    /// its transaction is pending. The same goes for a nonce that is being handed out.
+    fn resync(&self, address: Address, nonce: u64) -> bool {
+        self.nonces.remove_if(&address, |_, slot| slot.try_lock().is_some_and(|n| *n == nonce));
+        true
     }
 }
 
@@ -143,6 +292,14 @@ impl<M: NonceManager, N: Network> TxFiller<N> for NonceFiller<M> {
 
     fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
 
+    fn on_nonce_too_low(&self, tx: &N::TransactionRequest, nonce: u64) -> bool {
+        // nonces set by the caller are left alone
+        match (tx.nonce(), tx.from()) {
+            (None, Some(from)) => self.nonce_manager.resync(from, nonce),
+            _ => false,
+        }
+    }
+
     async fn prepare<P, T>(
         &self,
         provider: &P,
@@ -234,6 +391,67 @@ mod tests {
         assert_eq!(*filler.nonce_manager.nonces.get(&address).unwrap().value().lock().await, 4);
     }
 
+    #[tokio::test]
+    async fn reserve_and_release() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let manager = CachedNonceManager::default();
+        let filler = NonceFiller::new(manager.clone());
+        let address = Address::ZERO;
+
+        let lease = manager.reserve_nonce(&provider, address).await.unwrap();
+        assert_eq!(lease.nonce(), 0);
+        assert!(lease.release().await);
+
+        // The released nonce is handed out again, and the filler sees the reservation.
+        let lease = manager.reserve_nonce(&provider, address).await.unwrap();
+        assert_eq!(lease.nonce(), 0);
+        check_nonces(&filler, &provider, address, 1).await;
+
+        // Releasing would leave a gap once later nonces have been handed out.
+        assert!(!lease.release().await);
+        check_nonces(&filler, &provider, address, 6).await;
+    }
+
+    #[tokio::test]
+    async fn reset() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let filler = NonceFiller::<CachedNonceManager>::default();
+        let address = Address::ZERO;
+        check_nonces(&filler, &provider, address, 0).await;
+
+        // None of the nonces were used, so the resynced nonce starts over.
+        let lease = filler.nonce_manager.reserve_nonce(&provider, address).await.unwrap();
+        filler.nonce_manager.reset(address);
+        check_nonces(&filler, &provider, address, 0).await;
+
+        // Leases taken before the reset cannot be released.
+        assert!(!lease.release().await);
+    }
+
+    #[tokio::test]
+    async fn pending_block_id() {
+        let provider = ProviderBuilder::new()
+            .on_anvil_with_wallet_and_config(|anvil| anvil.arg("--no-mining"));
+        let from = provider.default_signer_address();
+        let tx = TransactionRequest {
+            from: Some(from),
+            value: Some(U256::from(100)),
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            nonce: Some(0),
+            gas_price: Some(20e9 as u128),
+            gas: Some(21000),
+            ..Default::default()
+        };
+        let _ = provider.send_transaction(tx).await.unwrap();
+
+        // The transaction is in the mempool but not mined.
+        let filler = NonceFiller::<CachedNonceManager>::default();
+        check_nonces(&filler, &provider, from, 0).await;
+        let filler =
+            NonceFiller::new(CachedNonceManager::default().with_block_id(BlockId::pending()));
+        check_nonces(&filler, &provider, from, 1).await;
+    }
+
     #[tokio::test]
     async fn no_nonce_if_sender_unset() {
         let provider = ProviderBuilder::new().with_cached_nonce_management().on_anvil();
@@ -250,6 +468,61 @@ mod tests {
         assert!(provider.send_transaction(tx).await.is_err());
     }
 
+    #[tokio::test]
+    async fn resync_nonce_too_low() {
+        let provider = ProviderBuilder::new().with_cached_nonce_management().on_anvil_with_wallet();
+        let tx = TransactionRequest {
+            value: Some(U256::from(100)),
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            gas_price: Some(20e9 as u128),
+            gas: Some(21000),
+            ..Default::default()
+        };
+        provider.send_transaction(tx.clone()).await.unwrap().get_receipt().await.unwrap();
+
+        // A nonce set by the caller bypasses the cache, which now holds a used nonce.
+        let used = TransactionRequest { nonce: Some(1), ..tx.clone() };
+        provider.send_transaction(used).await.unwrap().get_receipt().await.unwrap();
+
+        let tx_hash = provider.send_transaction(tx).await.unwrap().watch().await.unwrap();
+        let mined_tx = provider.get_transaction_by_hash(tx_hash).await.unwrap().unwrap();
+        assert_eq!(mined_tx.nonce, 2);
+    }
+
+    #[tokio::test]
+    async fn resync_concurrent_nonce_too_low() {
+        let provider = ProviderBuilder::new()
+            .with_cached_nonce_management()
+            .on_anvil_with_wallet_and_config(|anvil| anvil.arg("--no-mining"));
+        let tx = TransactionRequest {
+            value: Some(U256::from(100)),
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            gas_price: Some(20e9 as u128),
+            gas: Some(21000),
+            ..Default::default()
+        };
+        let _ = provider.send_transaction(tx.clone()).await.unwrap();
+        // Nonces set by the caller bypass the cache, which still holds nonce 0 once these are
+        // mined.
+        for nonce in 1..3 {
+            let used = TransactionRequest { nonce: Some(nonce), ..tx.clone() };
+            let _ = provider.send_transaction(used).await.unwrap();
+        }
+        provider.raw_request::<_, String>("evm_mine".into(), ()).await.unwrap();
+
+        // Both transactions are rejected with nonces 1 and 2, and resynced while the other one is
+        // still pending.
+        let (a, b) =
+            tokio::join!(provider.send_transaction(tx.clone()), provider.send_transaction(tx));
+        let mut nonces = Vec::new();
+        for pending in [a.unwrap(), b.unwrap()] {
+            let tx = provider.get_transaction_by_hash(*pending.tx_hash()).await.unwrap().unwrap();
+            nonces.push(tx.nonce);
+        }
+        nonces.sort_unstable();
+        assert_eq!(nonces, [3, 4]);
+    }
+
     #[tokio::test]
     async fn increments_nonce() {
         let provider = ProviderBuilder::new().with_cached_nonce_management().on_anvil_with_wallet();
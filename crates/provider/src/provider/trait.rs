@@ -16,20 +16,24 @@ use alloy_primitives::{
     hex, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, B256, U128,
     U256, U64,
 };
-use alloy_rpc_client::{ClientRef, NoParams, PollerBuilder, RpcCall, WeakClient};
+use alloy_rpc_client::{BatchRequest, ClientRef, NoParams, PollerBuilder, RpcCall, WeakClient};
 use alloy_rpc_types_eth::{
+    simulate::{SimulatePayload, SimulatedBlock},
     AccessListResult, BlockId, BlockNumberOrTag, EIP1186AccountProofResponse, FeeHistory, Filter,
     FilterChanges, Log, SyncStatus,
 };
 use alloy_transport::{BoxTransport, Transport, TransportResult};
 use serde_json::value::RawValue;
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::RangeInclusive};
 
 /// A task that polls the provider with `eth_getFilterChanges`, returning a list of `R`.
 ///
 /// See [`PollerBuilder`] for more details.
 pub type FilterPollerBuilder<T, R> = PollerBuilder<T, (U256,), Vec<R>>;
 
+/// The maximum number of blocks requested in a single batch by [`Provider::get_headers`].
+const MAX_HEADERS_PER_BATCH: u64 = 100;
+
 // todo: adjust docs
 // todo: reorder
 /// Provider is parameterized with a network and a transport. The default
@@ -158,6 +162,22 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         EthCall::new(self.weak_client(), tx)
     }
 
+    /// Executes an arbitrary number of transactions on top of the requested state.
+    ///
+    /// The transactions are packed into individual blocks, see [`SimulatePayload`]. Block and
+    /// state overrides can be provided per block.
+    ///
+    /// Defaults to the latest block. See also [`RpcWithBlock::block_id`].
+    ///
+    /// See also: <https://github.com/ethereum/execution-apis/pull/484>
+    #[doc(alias = "eth_simulateV1")]
+    fn simulate<'req>(
+        &self,
+        payload: &'req SimulatePayload,
+    ) -> RpcWithBlock<T, &'req SimulatePayload, Vec<SimulatedBlock<N::BlockResponse>>> {
+        RpcWithBlock::new(self.weak_client(), "eth_simulateV1", payload)
+    }
+
     /// Gets the chain ID.
     fn get_chain_id(&self) -> RpcCall<T, NoParams, U64, u64> {
         self.client().request_noparams("eth_chainId").map_resp(crate::utils::convert_u64)
@@ -323,6 +343,45 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(block)
     }
 
+    /// Gets the headers of the blocks in the given range, fetched in JSON-RPC batch requests.
+    ///
+    /// Each batch requests at most 100 blocks, and batches are sent one after the other, so large
+    /// ranges take proportionally longer rather than producing a single oversized request.
+    ///
+    /// Blocks that are not known to the node, e.g. because they are past the chain head, are
+    /// omitted from the result.
+    async fn get_headers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> TransportResult<Vec<N::HeaderResponse>> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (start, end) = range.into_inner();
+        let mut headers = Vec::new();
+        for chunk_start in (start..=end).step_by(MAX_HEADERS_PER_BATCH as usize) {
+            let chunk_end = chunk_start.saturating_add(MAX_HEADERS_PER_BATCH - 1).min(end);
+            let mut batch = BatchRequest::new(self.client());
+            let waiters = (chunk_start..=chunk_end)
+                .map(|number| {
+                    batch.add_call::<_, Option<N::BlockResponse>>(
+                        "eth_getBlockByNumber",
+                        &(BlockNumberOrTag::Number(number), false),
+                    )
+                })
+                .collect::<TransportResult<Vec<_>>>()?;
+            batch.send().await?;
+
+            for waiter in waiters {
+                if let Some(block) = waiter.await? {
+                    headers.push(block.header().clone());
+                }
+            }
+        }
+        Ok(headers)
+    }
+
     /// Gets the selected block [BlockId] receipts.
     async fn get_block_receipts(
         &self,
@@ -1283,6 +1342,61 @@ mod tests {
         assert_eq!(block.header.hash, hash);
     }
 
+    #[tokio::test]
+    async fn gets_headers() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let () = provider
+            .raw_request("anvil_mine".into(), (Some(U256::from(150)), None::<U256>))
+            .await
+            .unwrap();
+
+        // Spans two batches and goes past the chain head.
+        let headers = provider.get_headers(0..=200).await.unwrap();
+        assert_eq!(
+            headers.iter().map(|h| h.number).collect::<Vec<_>>(),
+            (0..=150).collect::<Vec<_>>()
+        );
+        let block = provider.get_block_by_number(120.into(), false).await.unwrap().unwrap();
+        assert_eq!(headers[120].hash, block.header.hash);
+
+        assert!(provider.get_headers(RangeInclusive::new(2, 1)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn simulate() {
+        use alloy_rpc_types_eth::simulate::SimBlock;
+
+        let call = TransactionRequest::default()
+            .from(address!("0000000000000000000000000000000000000001"))
+            .to(address!("0000000000000000000000000000000000000002"))
+            .input(bytes!("c0ffee").into());
+        let payload = SimulatePayload::default().extend(SimBlock::default().call(call));
+        let mut block: Block = Block::default();
+        block.header.number = 1;
+        let mut simulated = serde_json::to_value(&block).unwrap();
+        simulated["calls"] = serde_json::json!([{
+            "returnValue": "0x2a",
+            "logs": [],
+            "gasUsed": "0x5208",
+            "status": "0x1"
+        }]);
+        let recording = serde_json::json!([{
+            "method": "eth_simulateV1",
+            "params": [payload, "latest"],
+            "response": { "jsonrpc": "2.0", "id": 0, "result": [simulated] }
+        }]);
+        let provider = ProviderBuilder::new().on_replay(&recording.to_string());
+
+        let blocks = provider.simulate(&payload).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].inner.header.number, 1);
+        assert_eq!(blocks[0].calls.len(), 1);
+        assert_eq!(blocks[0].calls[0].return_value, bytes!("2a"));
+        assert_eq!(blocks[0].calls[0].gas_used, 21000);
+        assert!(blocks[0].calls[0].status);
+    }
+
     #[tokio::test]
     async fn gets_block_by_hash_with_raw_req() {
         init_tracing();
@@ -21,7 +21,8 @@ const MAX_RETRIES: usize = 3;
 ///
 /// This builder is used to create a poller task that repeatedly polls a method on a client and
 /// sends the responses to a channel. By default, this is done every 10 seconds, with a channel size
-/// of 16, and no limit on the number of successful polls. This is all configurable.
+/// of 16, no limit on the number of successful polls, and the first poll issued immediately. This
+/// is all configurable.
 ///
 /// The builder is consumed using the [`spawn`](Self::spawn) method, which returns a channel to
 /// receive the responses. The task will continue to poll until either the client or the channel is
@@ -68,6 +69,7 @@ pub struct PollerBuilder<Conn, Params, Resp> {
     // config options
     channel_size: usize,
     poll_interval: Duration,
+    initial_delay: Duration,
     limit: usize,
 
     _pd: PhantomData<fn() -> Resp>,
@@ -93,6 +95,7 @@ where
             params,
             channel_size: 16,
             poll_interval,
+            initial_delay: Duration::ZERO,
             limit: usize::MAX,
             _pd: PhantomData,
         }
@@ -146,6 +149,27 @@ where
         self
     }
 
+    /// Returns the delay before the first poll.
+    pub const fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    /// Sets the delay before the first poll.
+    ///
+    /// This can be used to stagger many pollers created at the same time, so that their requests
+    /// are spread out over the poll interval instead of being sent all at once.
+    pub fn set_initial_delay(&mut self, initial_delay: Duration) {
+        self.initial_delay = initial_delay;
+    }
+
+    /// Sets the delay before the first poll.
+    ///
+    /// See [`set_initial_delay`](Self::set_initial_delay) for more details.
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.set_initial_delay(initial_delay);
+        self
+    }
+
     /// Starts the poller in a new Tokio task, returning a channel to receive the responses on.
     pub fn spawn(self) -> PollChannel<Resp> {
         let (tx, rx) = broadcast::channel(self.channel_size);
@@ -153,6 +177,10 @@ where
         let fut = async move {
             let mut params = ParamsOnce::Typed(self.params);
             let mut retries = MAX_RETRIES;
+            if !self.initial_delay.is_zero() {
+                trace!(duration=?self.initial_delay, "delaying first poll");
+                tokio::time::sleep(self.initial_delay).await;
+            }
             'outer: for _ in 0..self.limit {
                 let Some(client) = self.client.upgrade() else {
                     debug!("client dropped");
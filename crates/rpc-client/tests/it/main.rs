@@ -3,6 +3,8 @@
 #[cfg(feature = "reqwest")]
 mod http;
 
+mod poller;
+
 #[cfg(feature = "pubsub")]
 mod ws;
 
@@ -0,0 +1,38 @@
+use alloy_primitives::U64;
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{layers::Recording, ReplayTransport};
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn block_number_client() -> RpcClient<ReplayTransport> {
+    let recording = Recording::from_json(
+        r#"[{"method":"eth_blockNumber","response":{"jsonrpc":"2.0","id":0,"result":"0x1"}}]"#,
+    )
+    .unwrap();
+    RpcClient::new(ReplayTransport::new(recording), true)
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_polls_immediately() {
+    let client = block_number_client();
+    let start = Instant::now();
+    let mut poller =
+        client.prepare_static_poller::<_, U64>("eth_blockNumber", ()).with_limit(Some(1)).spawn();
+
+    assert_eq!(poller.recv().await.unwrap(), U64::from(1));
+    assert_eq!(start.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_delays_the_first_poll() {
+    let client = block_number_client();
+    let start = Instant::now();
+    let mut poller = client
+        .prepare_static_poller::<_, U64>("eth_blockNumber", ())
+        .with_initial_delay(Duration::from_secs(5))
+        .with_limit(Some(1))
+        .spawn();
+
+    assert_eq!(poller.recv().await.unwrap(), U64::from(1));
+    assert_eq!(start.elapsed(), Duration::from_secs(5));
+}
@@ -0,0 +1,169 @@
+//! Types for the wallet call batching API defined in [EIP-5792].
+//!
+//! [EIP-5792]: https://eips.ethereum.org/EIPS/eip-5792
+
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{Address, BlockHash, Bytes, Log, TxHash, U256};
+use alloy_serde::OtherFields;
+
+/// Wallet capabilities, keyed by capability name, e.g. `paymasterService`.
+pub type Capabilities = OtherFields;
+
+/// A single call of a [`SendCallsRequest`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WalletCall {
+    /// The address the call is made to.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub to: Option<Address>,
+    /// The calldata of the call.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub data: Option<Bytes>,
+    /// The value sent with the call.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub value: Option<U256>,
+}
+
+/// The request of a `wallet_sendCalls` call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SendCallsRequest {
+    /// The version of the API format, currently `1.0`.
+    pub version: String,
+    /// The chain the calls are executed on.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub chain_id: u64,
+    /// The account sending the calls.
+    pub from: Address,
+    /// The calls to execute, in order.
+    pub calls: Vec<WalletCall>,
+    /// Capabilities requested for the calls, e.g. a paymaster service.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub capabilities: Option<Capabilities>,
+}
+
+impl SendCallsRequest {
+    /// Creates a new request for the given chain and sender, without calls.
+    pub fn new(chain_id: u64, from: Address) -> Self {
+        Self { version: "1.0".into(), chain_id, from, calls: Vec::new(), capabilities: None }
+    }
+
+    /// Appends a call to the request.
+    pub fn call(mut self, call: WalletCall) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Sets the requested capabilities.
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+}
+
+/// The status of a batch of calls sent with `wallet_sendCalls`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum CallStatus {
+    /// The calls have not been included onchain yet.
+    Pending,
+    /// The calls have been included onchain.
+    Confirmed,
+}
+
+/// The receipt of a transaction executing calls sent with `wallet_sendCalls`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallReceipt {
+    /// The logs emitted by the calls.
+    pub logs: Vec<Log>,
+    /// The status of the transaction, `1` for success and `0` for failure.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub status: u64,
+    /// The hash of the block the transaction was included in.
+    pub block_hash: BlockHash,
+    /// The number of the block the transaction was included in.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: u64,
+    /// The gas used by the transaction.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub gas_used: u128,
+    /// The hash of the transaction.
+    pub transaction_hash: TxHash,
+}
+
+/// The response of a `wallet_getCallsStatus` call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallsStatus {
+    /// The status of the calls.
+    pub status: CallStatus,
+    /// The receipts of the transactions executing the calls, once confirmed.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub receipts: Option<Vec<CallReceipt>>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serialize_send_calls_request() {
+        let request =
+            SendCallsRequest::new(1, address!("d46e8dd67c5d32be8058bb8eb970870f07244567")).call(
+                WalletCall {
+                    to: Some(address!("d46e8dd67c5d32be8058bb8eb970870f07244567")),
+                    value: Some(U256::from(0x9184e72a_u64)),
+                    data: Some(Bytes::from_static(&[0xfe])),
+                },
+            );
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": "1.0",
+                "chainId": "0x1",
+                "from": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+                "calls": [{
+                    "to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+                    "data": "0xfe",
+                    "value": "0x9184e72a"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_calls_status() {
+        let s = r#"{
+            "status": "CONFIRMED",
+            "receipts": [{
+                "logs": [{
+                    "address": "0xa922b54716264130634d6ff183747a8ead91a40b",
+                    "topics": ["0x5a2a90727cc9d000dd060b1132a5c977c9702bb3a52afe360c9c22f0e9451a68"],
+                    "data": "0xabcd"
+                }],
+                "status": "0x1",
+                "blockHash": "0xf19bbafd9fd0124ec110b848e8de4ab4f62bf60c189524e54213285e7f540d4a",
+                "blockNumber": "0xabcd",
+                "gasUsed": "0xdef",
+                "transactionHash": "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaff"
+            }]
+        }"#;
+        let status: CallsStatus = serde_json::from_str(s).unwrap();
+        assert_eq!(status.status, CallStatus::Confirmed);
+        let receipt = &status.receipts.as_ref().unwrap()[0];
+        assert_eq!(receipt.status, 1);
+        assert_eq!(receipt.block_number, 0xabcd);
+        assert_eq!(receipt.logs[0].address, address!("a922b54716264130634d6ff183747a8ead91a40b"));
+
+        let pending: CallsStatus = serde_json::from_str(r#"{"status":"PENDING"}"#).unwrap();
+        assert_eq!(pending, CallsStatus { status: CallStatus::Pending, receipts: None });
+    }
+}
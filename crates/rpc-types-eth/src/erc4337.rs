@@ -1,7 +1,7 @@
 use crate::{collections::HashMap, Log, TransactionReceipt};
 use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 /// Options for conditional raw transaction submissions.
 // reference for the implementation <https://notes.ethereum.org/@yoav/SkaX2lS9j#>
@@ -177,3 +177,311 @@ pub struct UserOperationGasEstimation {
     /// The gas limit for the call.
     pub call_gas_limit: U256,
 }
+
+/// Sponsor information returned by a paymaster service, as defined in ERC-7677.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaymasterSponsor {
+    /// The name of the sponsor.
+    pub name: String,
+    /// The optional icon of the sponsor, as a data URI.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub icon: Option<String>,
+}
+
+/// Response to `pm_getPaymasterStubData`, as defined in ERC-7677.
+///
+/// Entry point V0.6 services return `paymaster_and_data`, while entry point V0.7 services return
+/// `paymaster` and `paymaster_data` along with the paymaster gas limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PaymasterStubData {
+    /// The paymaster contract address (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster: Option<Address>,
+    /// The paymaster data (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_data: Option<Bytes>,
+    /// The paymaster address and data (entry point V0.6).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_and_data: Option<Bytes>,
+    /// The gas limit for the paymaster verification (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The gas limit for the paymaster post-operation (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    /// The sponsor of the user operation, if any.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub sponsor: Option<PaymasterSponsor>,
+    /// Whether the stub data is final, in which case `pm_getPaymasterData` does not need to be
+    /// called.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub is_final: Option<bool>,
+}
+
+/// Response to `pm_getPaymasterData`, as defined in ERC-7677.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PaymasterData {
+    /// The paymaster contract address (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster: Option<Address>,
+    /// The paymaster data (entry point V0.7).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_data: Option<Bytes>,
+    /// The paymaster address and data (entry point V0.6).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paymaster_and_data: Option<Bytes>,
+}
+
+impl SendUserOperation {
+    /// Applies the paymaster fields returned by a paymaster service to the user operation.
+    ///
+    /// Fields that the service did not return are left untouched.
+    pub fn apply_paymaster_data(&mut self, data: PaymasterData) {
+        match self {
+            Self::EntryPointV06(user_op) => {
+                if let Some(paymaster_and_data) = data.paymaster_and_data {
+                    user_op.paymaster_and_data = paymaster_and_data;
+                }
+            }
+            Self::EntryPointV07(packed_user_op) => {
+                if let Some(paymaster) = data.paymaster {
+                    packed_user_op.paymaster = paymaster;
+                }
+                if let Some(paymaster_data) = data.paymaster_data {
+                    packed_user_op.paymaster_data = paymaster_data;
+                }
+            }
+        }
+    }
+
+    /// Applies the paymaster stub data, including the paymaster gas limits, to the user
+    /// operation.
+    ///
+    /// This should be used before gas estimation, see [`PaymasterStubData`].
+    pub fn apply_paymaster_stub_data(&mut self, stub: PaymasterStubData) {
+        if let Self::EntryPointV07(packed_user_op) = self {
+            if let Some(limit) = stub.paymaster_verification_gas_limit {
+                packed_user_op.paymaster_verification_gas_limit = limit;
+            }
+            if let Some(limit) = stub.paymaster_post_op_gas_limit {
+                packed_user_op.paymaster_post_op_gas_limit = limit;
+            }
+        }
+        self.apply_paymaster_data(PaymasterData {
+            paymaster: stub.paymaster,
+            paymaster_data: stub.paymaster_data,
+            paymaster_and_data: stub.paymaster_and_data,
+        });
+    }
+}
+
+impl PaymasterStubData {
+    /// Returns the address of the paymaster sponsoring the user operation, if any.
+    pub fn paymaster_address(&self) -> Option<Address> {
+        paymaster_address(self.paymaster, self.paymaster_and_data.as_ref())
+    }
+}
+
+impl PaymasterData {
+    /// Returns the address of the paymaster sponsoring the user operation, if any.
+    pub fn paymaster_address(&self) -> Option<Address> {
+        paymaster_address(self.paymaster, self.paymaster_and_data.as_ref())
+    }
+}
+
+/// Returns the paymaster address, or the address at the start of `paymaster_and_data` for entry
+/// point V0.6.
+fn paymaster_address(
+    paymaster: Option<Address>,
+    paymaster_and_data: Option<&Bytes>,
+) -> Option<Address> {
+    paymaster
+        .or_else(|| paymaster_and_data.and_then(|data| data.get(..20)).map(Address::from_slice))
+        .filter(|paymaster| !paymaster.is_zero())
+}
+
+/// Limits on the sponsorship returned by a paymaster service.
+///
+/// The paymaster data returned by a service ends up in the signed user operation, so it should be
+/// checked before being applied with [`SendUserOperation::apply_paymaster_stub_data`] or
+/// [`SendUserOperation::apply_paymaster_data`]. Limits that are not set are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorshipLimits {
+    /// The paymasters allowed to sponsor the user operation.
+    pub paymasters: Option<Vec<Address>>,
+    /// The maximum paymaster verification gas limit.
+    pub max_paymaster_verification_gas_limit: Option<U256>,
+    /// The maximum paymaster post-operation gas limit.
+    pub max_paymaster_post_op_gas_limit: Option<U256>,
+}
+
+impl SponsorshipLimits {
+    /// Only allows the given paymaster to sponsor the user operation, in addition to previously
+    /// allowed ones.
+    pub fn with_paymaster(mut self, paymaster: Address) -> Self {
+        self.paymasters.get_or_insert_with(Vec::new).push(paymaster);
+        self
+    }
+
+    /// Sets the maximum paymaster verification gas limit.
+    pub const fn with_max_paymaster_verification_gas_limit(mut self, limit: U256) -> Self {
+        self.max_paymaster_verification_gas_limit = Some(limit);
+        self
+    }
+
+    /// Sets the maximum paymaster post-operation gas limit.
+    pub const fn with_max_paymaster_post_op_gas_limit(mut self, limit: U256) -> Self {
+        self.max_paymaster_post_op_gas_limit = Some(limit);
+        self
+    }
+
+    /// Checks the stub data returned by `pm_getPaymasterStubData` against the limits.
+    pub fn check_stub_data(&self, stub: &PaymasterStubData) -> Result<(), SponsorshipError> {
+        self.check_paymaster(stub.paymaster_address())?;
+        check_gas_limit(
+            stub.paymaster_verification_gas_limit,
+            self.max_paymaster_verification_gas_limit,
+            |limit, max| SponsorshipError::VerificationGasLimitExceeded { limit, max },
+        )?;
+        check_gas_limit(
+            stub.paymaster_post_op_gas_limit,
+            self.max_paymaster_post_op_gas_limit,
+            |limit, max| SponsorshipError::PostOpGasLimitExceeded { limit, max },
+        )
+    }
+
+    /// Checks the data returned by `pm_getPaymasterData` against the limits.
+    pub fn check_data(&self, data: &PaymasterData) -> Result<(), SponsorshipError> {
+        self.check_paymaster(data.paymaster_address())
+    }
+
+    fn check_paymaster(&self, paymaster: Option<Address>) -> Result<(), SponsorshipError> {
+        let paymaster = paymaster.ok_or(SponsorshipError::NotSponsored)?;
+        match &self.paymasters {
+            Some(paymasters) if !paymasters.contains(&paymaster) => {
+                Err(SponsorshipError::PaymasterNotAllowed(paymaster))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_gas_limit(
+    limit: Option<U256>,
+    max: Option<U256>,
+    err: impl FnOnce(U256, U256) -> SponsorshipError,
+) -> Result<(), SponsorshipError> {
+    match (limit, max) {
+        (Some(limit), Some(max)) if limit > max => Err(err(limit, max)),
+        _ => Ok(()),
+    }
+}
+
+/// Error returned when paymaster data exceeds the [`SponsorshipLimits`].
+#[derive(Clone, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum SponsorshipError {
+    /// The paymaster service did not return a paymaster.
+    #[display("user operation is not sponsored")]
+    NotSponsored,
+    /// The returned paymaster is not one of the allowed paymasters.
+    #[display("paymaster {_0} is not allowed")]
+    PaymasterNotAllowed(Address),
+    /// The returned paymaster verification gas limit is too high.
+    #[display("paymaster verification gas limit {limit} exceeds {max}")]
+    VerificationGasLimitExceeded {
+        /// The returned gas limit.
+        limit: U256,
+        /// The maximum gas limit.
+        max: U256,
+    },
+    /// The returned paymaster post-operation gas limit is too high.
+    #[display("paymaster post-operation gas limit {limit} exceeds {max}")]
+    PostOpGasLimitExceeded {
+        /// The returned gas limit.
+        limit: U256,
+        /// The maximum gas limit.
+        max: U256,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SponsorshipError {}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn deserialize_paymaster_stub_data() {
+        let s = r#"{
+            "paymaster": "0x0000000000000000000000000000000000000001",
+            "paymasterData": "0x1234",
+            "paymasterVerificationGasLimit": "0x10000",
+            "paymasterPostOpGasLimit": "0x5000",
+            "sponsor": { "name": "My App" },
+            "isFinal": false
+        }"#;
+        let stub: PaymasterStubData = serde_json::from_str(s).unwrap();
+        assert_eq!(stub.paymaster, Some(address!("0000000000000000000000000000000000000001")));
+        assert_eq!(stub.paymaster_post_op_gas_limit, Some(U256::from(0x5000)));
+        assert_eq!(stub.sponsor.as_ref().map(|s| s.name.as_str()), Some("My App"));
+        assert_eq!(stub.is_final, Some(false));
+        assert!(stub.paymaster_and_data.is_none());
+    }
+
+    #[test]
+    fn deserialize_paymaster_data_v06() {
+        let s = r#"{ "paymasterAndData": "0xabcd" }"#;
+        let data: PaymasterData = serde_json::from_str(s).unwrap();
+        assert_eq!(data.paymaster_and_data, Some(Bytes::from_static(&[0xab, 0xcd])));
+        assert!(data.paymaster.is_none());
+    }
+
+    #[test]
+    fn sponsorship_limits() {
+        let paymaster = address!("0000000000000000000000000000000000000001");
+        let limits = SponsorshipLimits::default()
+            .with_paymaster(paymaster)
+            .with_max_paymaster_verification_gas_limit(U256::from(0x10000))
+            .with_max_paymaster_post_op_gas_limit(U256::from(0x1000));
+        let stub = PaymasterStubData {
+            paymaster: Some(paymaster),
+            paymaster_verification_gas_limit: Some(U256::from(0x10000)),
+            paymaster_post_op_gas_limit: Some(U256::from(0x5000)),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits.check_stub_data(&stub),
+            Err(SponsorshipError::PostOpGasLimitExceeded {
+                limit: U256::from(0x5000),
+                max: U256::from(0x1000)
+            })
+        );
+        assert_eq!(
+            limits
+                .clone()
+                .with_max_paymaster_post_op_gas_limit(U256::from(0x5000))
+                .check_stub_data(&stub),
+            Ok(())
+        );
+
+        // Entry point V0.6 paymasters are read from `paymaster_and_data`.
+        let other = address!("0000000000000000000000000000000000000002");
+        let data = PaymasterData {
+            paymaster_and_data: Some([other.as_slice(), &[0xab]].concat().into()),
+            ..Default::default()
+        };
+        assert_eq!(limits.check_data(&data), Err(SponsorshipError::PaymasterNotAllowed(other)));
+        assert_eq!(SponsorshipLimits::default().check_data(&data), Ok(()));
+        assert_eq!(
+            SponsorshipLimits::default().check_data(&PaymasterData::default()),
+            Err(SponsorshipError::NotSponsored)
+        );
+    }
+}
@@ -540,6 +540,16 @@ impl Filter {
     pub fn has_topics(&self) -> bool {
         self.topics.iter().any(|t| !t.is_empty())
     }
+
+    /// Returns `true` if a block with the given logs bloom may contain logs matching the addresses
+    /// and topics of this filter.
+    ///
+    /// Blooms can produce false positives but no false negatives, so if this returns `false` the
+    /// block can be skipped without fetching its logs.
+    pub fn matches_bloom(&self, bloom: Bloom) -> bool {
+        FilteredParams::matches_address(bloom, &FilteredParams::address_filter(&self.address))
+            && FilteredParams::matches_topics(bloom, &FilteredParams::topics_filter(&self.topics))
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -1518,6 +1528,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn filter_matches_bloom() {
+        let address = Address::random();
+        let topic = B256::random();
+        let bloom = build_bloom(address, topic, B256::random());
+
+        assert!(Filter::new().matches_bloom(bloom));
+        assert!(Filter::new().address(address).event_signature(topic).matches_bloom(bloom));
+        assert!(Filter::new()
+            .address(vec![Address::random(), address])
+            .event_signature(topic)
+            .matches_bloom(bloom));
+        assert!(!Filter::new().address(Address::random()).matches_bloom(bloom));
+        assert!(!Filter::new().address(address).topic1(B256::random()).matches_bloom(bloom));
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn can_convert_to_ethers_filter() {
@@ -78,4 +78,6 @@ pub use erc4337::{
     UserOperationGasEstimation, UserOperationReceipt,
 };
 
+pub mod eip5792;
+
 pub mod simulate;
@@ -1,6 +1,8 @@
 use alloy_consensus::SignableTransaction;
 use alloy_primitives::{hex, Address, ChainId, B256};
-use alloy_signer::{sign_transaction_with_chain_id, Result, Signature, Signer};
+use alloy_signer::{
+    sign_transaction_with_chain_id, utils::assemble_eth_signature, Result, Signature, Signer,
+};
 use async_trait::async_trait;
 use gcloud_sdk::{
     google::cloud::kms::{
@@ -220,7 +222,7 @@ impl GcpSigner {
     #[instrument(err, skip(digest), fields(digest = %hex::encode(digest)))]
     async fn sign_digest_inner(&self, digest: &B256) -> Result<Signature, GcpSignerError> {
         let sig = self.sign_digest(digest).await?;
-        let mut sig = sig_from_digest_bytes_trial_recovery(sig, digest, &self.pubkey);
+        let mut sig = assemble_eth_signature(digest, &sig, &self.pubkey)?;
         if let Some(chain_id) = self.chain_id {
             sig = sig.with_chain_id(chain_id);
         }
@@ -275,30 +277,6 @@ fn decode_signature(raw: Vec<u8>) -> Result<ecdsa::Signature, GcpSignerError> {
     Ok(sig.normalize_s().unwrap_or(sig))
 }
 
-/// Recover an RSig from a signature under a known key by trial/error.
-fn sig_from_digest_bytes_trial_recovery(
-    sig: ecdsa::Signature,
-    hash: &B256,
-    pubkey: &VerifyingKey,
-) -> Signature {
-    let signature = Signature::from_signature_and_parity(sig, false).unwrap();
-    if check_candidate(&signature, hash, pubkey) {
-        return signature;
-    }
-
-    let signature = signature.with_parity(true);
-    if check_candidate(&signature, hash, pubkey) {
-        return signature;
-    }
-
-    panic!("bad sig");
-}
-
-/// Makes a trial recovery to check whether an RSig corresponds to a known `VerifyingKey`.
-fn check_candidate(signature: &Signature, hash: &B256, pubkey: &VerifyingKey) -> bool {
-    signature.recover_from_prehash(hash).map(|key| key == *pubkey).unwrap_or(false)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,9 @@
 //! Utility functions for working with Ethereum signatures.
 
-use alloy_primitives::{keccak256, Address};
+use alloy_primitives::{keccak256, Address, Signature, SignatureError, B256};
 use elliptic_curve::sec1::ToEncodedPoint;
 use k256::{
-    ecdsa::{SigningKey, VerifyingKey},
+    ecdsa::{self, RecoveryId, SigningKey, VerifyingKey},
     AffinePoint,
 };
 
@@ -40,10 +40,70 @@ pub fn raw_public_key_to_address(pubkey: &[u8]) -> Address {
     Address::from_slice(&digest[12..])
 }
 
+/// Assembles an Ethereum [`Signature`] from an ECDSA signature over `hash` made by `pubkey`.
+///
+/// Remote signers, such as cloud KMS services or threshold signing protocols, only return the
+/// `(r, s)` pair. The y-parity required for public key recovery is determined by trial recovery
+/// against the known `pubkey`.
+///
+/// High `s` values are normalized first, as required by [EIP-2]. The result is therefore
+/// deterministic: the same `hash` and `pubkey` always yield the same signature, whether the
+/// signer returned the low-`s` or the high-`s` form. Raw 64-byte `r || s` signatures can be
+/// converted with [`ecdsa::Signature::from_slice`].
+///
+/// # Errors
+///
+/// Returns an error if `signature` is not a signature of `hash` by `pubkey`.
+///
+/// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+pub fn assemble_eth_signature(
+    hash: &B256,
+    signature: &ecdsa::Signature,
+    pubkey: &VerifyingKey,
+) -> Result<Signature, ecdsa::Error> {
+    let signature = signature.normalize_s().unwrap_or(*signature);
+    [false, true]
+        .into_iter()
+        .map(|y_parity| Signature::from((signature, RecoveryId::new(y_parity, false))))
+        .find(|candidate| candidate.recover_from_prehash(hash).is_ok_and(|key| key == *pubkey))
+        .ok_or_else(ecdsa::Error::new)
+}
+
+/// Encodes signatures over the same `hash` by multiple signers into the concatenated form expected
+/// by multisig contracts such as the Safe.
+///
+/// The signer of each signature is recovered from `hash`. Signatures are ordered by ascending
+/// signer address, as required by the Safe to reject duplicate signers, and signatures from the
+/// same signer are only included once. Each signature is encoded as `r || s || v` with `v` being
+/// 27 or 28, regardless of whether the signature carries an EIP-155 chain id, as the Safe
+/// interprets other values of `v` as contract signatures or approved hashes.
+///
+/// # Errors
+///
+/// Returns an error if the signer of a signature cannot be recovered.
+pub fn encode_multisig_signatures(
+    hash: &B256,
+    signatures: &[Signature],
+) -> Result<Vec<u8>, SignatureError> {
+    let mut signed = signatures
+        .iter()
+        .map(|signature| Ok((signature.recover_address_from_prehash(hash)?, signature)))
+        .collect::<Result<Vec<_>, SignatureError>>()?;
+    signed.sort_by_key(|(signer, _)| *signer);
+    signed.dedup_by_key(|(signer, _)| *signer);
+    let mut encoded = Vec::with_capacity(signed.len() * 65);
+    for (_, signature) in signed {
+        encoded.extend_from_slice(&signature.r().to_be_bytes::<32>());
+        encoded.extend_from_slice(&signature.s().to_be_bytes::<32>());
+        encoded.push(27 + signature.v().y_parity() as u8);
+    }
+    Ok(encoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::hex;
+    use alloy_primitives::{b256, hex};
 
     // Only tests for correctness, no edge cases. Uses examples from https://docs.ethers.org/v5/api/utils/address/#utils-computeAddress
     #[test]
@@ -77,4 +137,76 @@ mod tests {
     fn test_raw_public_key_to_address_panics() {
         raw_public_key_to_address(&[]);
     }
+
+    #[test]
+    fn test_assemble_eth_signature() {
+        let hash = b256!("5c54bfe3f2c7c0bd3e2b3e5dc5b4fd5b3b4f0a2e4b24a0bfa3c0f1bd2b1a5e6f");
+        for key in [
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        ] {
+            let key = SigningKey::from_slice(&hex::decode(key).unwrap()).unwrap();
+            let (signature, recid) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+            let expected = Signature::from((signature, recid));
+            let pubkey = key.verifying_key();
+
+            let assembled = assemble_eth_signature(&hash, &signature, pubkey).unwrap();
+            assert_eq!(assembled, expected);
+            assert_eq!(
+                assembled.recover_address_from_prehash(&hash).unwrap(),
+                secret_key_to_address(&key)
+            );
+
+            // The high-s form of the same signature assembles to the same result.
+            let (r, s) = signature.split_scalars();
+            let high_s = ecdsa::Signature::from_scalars(r, -*s).unwrap();
+            assert_eq!(assemble_eth_signature(&hash, &high_s, pubkey).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_assemble_eth_signature_wrong_key() {
+        let hash = keccak256("hello");
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let other = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (signature, _) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+
+        assert!(assemble_eth_signature(&hash, &signature, other.verifying_key()).is_err());
+        let other_hash = keccak256("world");
+        assert!(assemble_eth_signature(&other_hash, &signature, key.verifying_key()).is_err());
+    }
+
+    #[test]
+    fn test_encode_multisig_signatures() {
+        let hash = keccak256("hello");
+        let keys =
+            [SigningKey::from_slice(&[1; 32]).unwrap(), SigningKey::from_slice(&[2; 32]).unwrap()];
+        let signatures = keys
+            .iter()
+            .map(|key| {
+                let (signature, recid) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+                Signature::from((signature, recid))
+            })
+            .collect::<Vec<_>>();
+        let mut signers = keys.iter().map(secret_key_to_address).collect::<Vec<_>>();
+        signers.sort();
+
+        let encoded =
+            encode_multisig_signatures(&hash, &[signatures[1], signatures[0], signatures[1]])
+                .unwrap();
+        assert_eq!(encoded.len(), 2 * 65);
+        for (chunk, signer) in encoded.chunks(65).zip(&signers) {
+            assert!(chunk[64] == 27 || chunk[64] == 28);
+            let signature = Signature::try_from(chunk).unwrap();
+            assert_eq!(signature.recover_address_from_prehash(&hash).unwrap(), *signer);
+        }
+
+        // Signatures carrying a chain id, as produced by signers with a chain id set, encode the
+        // same way.
+        let eip155 =
+            signatures.iter().map(|signature| signature.with_chain_id(1)).collect::<Vec<_>>();
+        assert!(eip155.iter().all(|signature| signature.v().chain_id() == Some(1)));
+        assert_eq!(encode_multisig_signatures(&hash, &eip155).unwrap(), encoded);
+    }
 }
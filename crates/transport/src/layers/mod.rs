@@ -1,5 +1,10 @@
 //! Module for housing transport layers.
 
+#[cfg(feature = "replay")]
+mod record;
+#[cfg(feature = "replay")]
+pub use record::{RecordLayer, RecordService, RecordedCall, Recording};
+
 mod retry;
 
 /// RetryBackoffLayer
@@ -0,0 +1,228 @@
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{Id, RequestPacket, Response, ResponsePacket, SerializedRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::{value::RawValue, Number, Value};
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// A single request/response exchange captured by the [`RecordLayer`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedCall {
+    /// The method of the request.
+    pub method: String,
+    /// The params of the request, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Box<RawValue>>,
+    /// The response returned by the transport.
+    pub response: Response,
+}
+
+impl RecordedCall {
+    /// Returns `true` if this call was made with the same method and params as the given request.
+    ///
+    /// Params are compared in a canonical form, so that logically identical params serialized by
+    /// different clients still match:
+    /// - object keys are sorted and insignificant whitespace is removed;
+    /// - integral numbers are compared by value, e.g. `1.0` matches `1`;
+    /// - hex strings are compared case-insensitively, e.g. checksummed and lowercase addresses
+    ///   match.
+    ///
+    /// Leading zeros are significant, as a quantity cannot be told apart from a byte string:
+    /// `"0x01"` does not match `"0x1"`, just like calldata `"0x00fd"` does not match `"0xfd"`.
+    /// Decimal strings and non-integral numbers are compared verbatim.
+    pub fn matches(&self, req: &SerializedRequest) -> bool {
+        self.method == req.method()
+            && self.params.as_deref().map(canonicalize) == req.params().map(canonicalize)
+    }
+}
+
+/// Returns the canonical form of a JSON value, or the raw JSON if it cannot be parsed.
+fn canonicalize(raw: &RawValue) -> String {
+    fn normalize(value: Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut entries = map.into_iter().collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                Value::Object(entries.into_iter().map(|(k, v)| (k, normalize(v))).collect())
+            }
+            Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+            Value::Number(n) => Value::Number(normalize_number(n)),
+            Value::String(s) => Value::String(normalize_hex(s)),
+            value => value,
+        }
+    }
+
+    serde_json::from_str(raw.get())
+        .map_or_else(|_| raw.get().to_string(), |value| normalize(value).to_string())
+}
+
+/// Converts floats with no fractional part that fit in an integer to that integer.
+fn normalize_number(n: Number) -> Number {
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f.fract() == 0.0 => {
+            if (0.0..u64::MAX as f64).contains(&f) {
+                Number::from(f as u64)
+            } else if (i64::MIN as f64..0.0).contains(&f) {
+                Number::from(f as i64)
+            } else {
+                n
+            }
+        }
+        _ => n,
+    }
+}
+
+/// Lowercases a `0x`-prefixed hex string, leaving other strings untouched.
+fn normalize_hex(s: String) -> String {
+    let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) else { return s };
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return s;
+    }
+    s.to_ascii_lowercase()
+}
+
+/// A shared, ordered log of [`RecordedCall`]s.
+///
+/// Cloning a `Recording` yields a handle to the same log, so it can be kept by the caller while
+/// the [`RecordLayer`] appends to it. A recording can be serialized to JSON and later served by
+/// the [`ReplayTransport`](crate::ReplayTransport).
+#[derive(Clone, Debug, Default)]
+pub struct Recording(Arc<Mutex<Vec<RecordedCall>>>);
+
+impl Recording {
+    /// Creates a new, empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recording from a list of calls.
+    pub fn from_calls(calls: Vec<RecordedCall>) -> Self {
+        Self(Arc::new(Mutex::new(calls)))
+    }
+
+    /// Returns a copy of the recorded calls, in the order they completed.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns the number of recorded calls.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Returns `true` if nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    /// Removes all recorded calls.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    /// Serializes the recorded calls to a JSON string.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&*self.0.lock().unwrap())
+    }
+
+    /// Deserializes a recording from a JSON string produced by [`Recording::to_json`].
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s).map(Self::from_calls)
+    }
+
+    fn push(&self, call: RecordedCall) {
+        self.0.lock().unwrap().push(call);
+    }
+}
+
+/// A Transport Layer that records every request and its response into a
+/// [`Recording`].
+///
+/// Only exchanges that produced a response are recorded. Transport errors are
+/// passed through untouched.
+#[derive(Clone, Debug, Default)]
+pub struct RecordLayer {
+    recording: Recording,
+}
+
+impl RecordLayer {
+    /// Creates a new record layer appending to the given recording.
+    pub const fn new(recording: Recording) -> Self {
+        Self { recording }
+    }
+
+    /// Returns a handle to the recording of this layer.
+    pub fn recording(&self) -> Recording {
+        self.recording.clone()
+    }
+}
+
+impl<S> Layer<S> for RecordLayer {
+    type Service = RecordService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RecordService { inner, recording: self.recording.clone() }
+    }
+}
+
+/// A Tower Service used by the [`RecordLayer`] that records every request and
+/// its response.
+#[derive(Clone, Debug)]
+pub struct RecordService<S> {
+    /// The inner service
+    inner: S,
+    /// The recording to append to
+    recording: Recording,
+}
+
+impl<S> Service<RequestPacket> for RecordService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let requests: Vec<(Id, String, Option<Box<RawValue>>)> = match &request {
+            RequestPacket::Single(req) => vec![request_parts(req)],
+            RequestPacket::Batch(reqs) => reqs.iter().map(request_parts).collect(),
+        };
+        let recording = self.recording.clone();
+        let fut = self.inner.call(request);
+        Box::pin(async move {
+            let resp = fut.await?;
+            let responses = match &resp {
+                ResponsePacket::Single(resp) => std::slice::from_ref(resp),
+                ResponsePacket::Batch(resps) => resps.as_slice(),
+            };
+            for response in responses {
+                if let Some((_, method, params)) =
+                    requests.iter().find(|(id, ..)| *id == response.id)
+                {
+                    recording.push(RecordedCall {
+                        method: method.clone(),
+                        params: params.clone(),
+                        response: response.clone(),
+                    });
+                }
+            }
+            Ok(resp)
+        })
+    }
+}
+
+fn request_parts(req: &SerializedRequest) -> (Id, String, Option<Box<RawValue>>) {
+    (req.id().clone(), req.method().to_string(), req.params().map(ToOwned::to_owned))
+}
@@ -20,6 +20,11 @@ mod error;
 pub use error::TransportErrorKind;
 pub use error::{HttpError, TransportError, TransportResult};
 
+#[cfg(feature = "replay")]
+mod replay;
+#[cfg(feature = "replay")]
+pub use replay::ReplayTransport;
+
 mod r#trait;
 pub use r#trait::Transport;
 
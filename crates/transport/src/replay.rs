@@ -0,0 +1,425 @@
+use crate::{
+    layers::{RecordedCall, Recording},
+    TransportError, TransportErrorKind, TransportFut,
+};
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::Service;
+
+/// A transport that serves responses from a [`Recording`] instead of a network
+/// connection.
+///
+/// Each incoming request is answered by the first not-yet-served recorded call
+/// with the same method and params, in recording order. Params are matched
+/// regardless of object key order and whitespace. The response id is
+/// rewritten to match the incoming request. Requests without a matching
+/// recorded call fail with a [`TransportErrorKind::Custom`] error.
+///
+/// Combined with the [`RecordLayer`](crate::layers::RecordLayer), this allows
+/// reproducing a session deterministically without a node.
+///
+/// The calls being served can be changed while the transport is in use, which
+/// allows scripting chain reorganizations: serve the canonical chain up to some
+/// block, then [`replace`](Self::replace) the remaining calls with a recording
+/// of a divergent chain.
+///
+/// Transport failures and latency can be injected with
+/// [`fail_nth`](Self::fail_nth), [`fail_with_probability`](Self::fail_with_probability)
+/// and [`set_latency`](Self::set_latency), to exercise retry and timeout
+/// handling.
+///
+/// ```
+/// use alloy_transport::{layers::Recording, ReplayTransport};
+///
+/// # fn example(json: &str) -> serde_json::Result<()> {
+/// let transport = ReplayTransport::new(Recording::from_json(json)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplayTransport {
+    calls: Arc<Mutex<Vec<Option<RecordedCall>>>>,
+    faults: Arc<Mutex<Faults>>,
+}
+
+/// A failure injected into a [`ReplayTransport`].
+type Failure = Box<dyn FnOnce() -> TransportError + Send>;
+
+/// The failures and latency injected into a [`ReplayTransport`].
+#[derive(Default)]
+struct Faults {
+    /// The number of requests sent so far.
+    requests: usize,
+    /// Failures keyed by the index of the request they apply to.
+    failures: Vec<(usize, Failure)>,
+    /// Failures applied to requests at random.
+    random: Option<RandomFailure>,
+    /// The delay before each response.
+    latency: Duration,
+}
+
+impl fmt::Debug for Faults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Faults")
+            .field("requests", &self.requests)
+            .field("failures", &self.failures.iter().map(|(n, _)| n).collect::<Vec<_>>())
+            .field("probability", &self.random.as_ref().map(|random| random.probability))
+            .field("latency", &self.latency)
+            .finish()
+    }
+}
+
+/// A failure applied to requests at random, see
+/// [`ReplayTransport::fail_with_probability`].
+struct RandomFailure {
+    /// The probability of a request failing.
+    probability: f64,
+    /// The state of the pseudo-random sequence.
+    state: u64,
+    /// The error of failed requests.
+    err: Box<dyn Fn() -> TransportError + Send>,
+}
+
+impl RandomFailure {
+    /// Returns `true` if the next request fails.
+    ///
+    /// Draws from a [SplitMix64] sequence, which is reproducible across
+    /// platforms and releases.
+    ///
+    /// [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+    fn fails(&mut self) -> bool {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        // the top 53 bits as a float in `[0, 1)`
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < self.probability
+    }
+}
+
+impl ReplayTransport {
+    /// Creates a new replay transport serving the calls of the given recording.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            calls: Arc::new(Mutex::new(recording.calls().into_iter().map(Some).collect())),
+            faults: Default::default(),
+        }
+    }
+
+    /// Makes the `n`-th request sent from now on fail with the error returned
+    /// by `err`, `0` being the next request.
+    ///
+    /// A batch counts as a single request. The failed request does not
+    /// consume a recorded call.
+    pub fn fail_nth(&self, n: usize, err: impl FnOnce() -> TransportError + Send + 'static) {
+        let mut faults = self.faults.lock().unwrap();
+        let index = faults.requests + n;
+        faults.failures.push((index, Box::new(err)));
+    }
+
+    /// Makes every request sent from now on fail with the given
+    /// `probability`, with the error returned by `err`.
+    ///
+    /// Failures are drawn from a pseudo-random sequence seeded with `seed`, so
+    /// the same seed fails the same requests on every run. A batch counts as a
+    /// single request, and failed requests do not consume a recorded call.
+    /// Failures scheduled with [`fail_nth`](Self::fail_nth) take precedence.
+    /// Replaces any previous probability; a probability of `0.0` disables
+    /// random failures.
+    pub fn fail_with_probability(
+        &self,
+        probability: f64,
+        seed: u64,
+        err: impl Fn() -> TransportError + Send + 'static,
+    ) {
+        self.faults.lock().unwrap().random =
+            Some(RandomFailure { probability, state: seed, err: Box::new(err) });
+    }
+
+    /// Delays every response by the given duration.
+    pub fn set_latency(&self, latency: Duration) {
+        self.faults.lock().unwrap().latency = latency;
+    }
+
+    /// Appends the calls of the given recording, to be served after the
+    /// current ones.
+    pub fn extend(&self, recording: Recording) {
+        self.calls.lock().unwrap().extend(recording.calls().into_iter().map(Some));
+    }
+
+    /// Discards all calls that have not been served yet, and serves the calls
+    /// of the given recording instead.
+    pub fn replace(&self, recording: Recording) {
+        *self.calls.lock().unwrap() = recording.calls().into_iter().map(Some).collect();
+    }
+
+    /// Returns the number of recorded calls that have not been served yet.
+    pub fn remaining(&self) -> usize {
+        self.calls.lock().unwrap().iter().filter(|c| c.is_some()).count()
+    }
+
+    /// Serves the given requests, consuming one recorded call for each.
+    ///
+    /// Calls are only consumed if every request has a match, so a failed
+    /// batch can be retried.
+    fn replay(&self, reqs: &[SerializedRequest]) -> Result<Vec<Response>, TransportError> {
+        let mut calls = self.calls.lock().unwrap();
+        let mut matched = Vec::with_capacity(reqs.len());
+        for req in reqs {
+            let index = calls
+                .iter()
+                .enumerate()
+                .position(|(i, c)| {
+                    !matched.contains(&i) && c.as_ref().is_some_and(|c| c.matches(req))
+                })
+                .ok_or_else(|| {
+                    TransportErrorKind::custom_str(&format!(
+                        "no recorded response for {} {}",
+                        req.method(),
+                        req.params().map(|p| p.get()).unwrap_or_default()
+                    ))
+                })?;
+            matched.push(index);
+        }
+        Ok(matched
+            .into_iter()
+            .zip(reqs)
+            .map(|(index, req)| {
+                let mut response = calls[index].take().expect("matched call").response;
+                response.id = req.id().clone();
+                response
+            })
+            .collect())
+    }
+}
+
+impl Service<RequestPacket> for ReplayTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let (failure, latency) = {
+            let mut faults = self.faults.lock().unwrap();
+            let index = faults.requests;
+            faults.requests += 1;
+            // drawn for every request, so the sequence does not depend on `fail_nth`
+            let random =
+                faults.random.as_mut().and_then(|random| random.fails().then(|| (random.err)()));
+            let failure = faults
+                .failures
+                .iter()
+                .position(|(n, _)| *n == index)
+                .map(|i| faults.failures.swap_remove(i).1());
+            (failure.or(random), faults.latency)
+        };
+
+        let res = match (failure, &request) {
+            (Some(err), _) => Err(err),
+            (None, RequestPacket::Single(req)) => self
+                .replay(std::slice::from_ref(req))
+                .map(|mut resps| ResponsePacket::Single(resps.remove(0))),
+            (None, RequestPacket::Batch(reqs)) => self.replay(reqs).map(ResponsePacket::Batch),
+        };
+        Box::pin(async move {
+            if !latency.is_zero() {
+                tokio::time::sleep(latency).await;
+            }
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::{RecordLayer, RetryBackoffLayer};
+    use alloy_json_rpc::{Id, Request, ResponsePayload};
+    use futures_util::FutureExt;
+    use tower::Layer;
+
+    fn request(id: u64, method: &'static str, params: u64) -> RequestPacket {
+        Request::new(method, Id::Number(id), (params,)).serialize().unwrap().into()
+    }
+
+    fn single(resp: ResponsePacket) -> Response {
+        match resp {
+            ResponsePacket::Single(resp) => resp,
+            ResponsePacket::Batch(_) => panic!("expected a single response"),
+        }
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let recording = Recording::new();
+        let source = ReplayTransport::new(Recording::from_json(
+            r#"[{"method":"eth_getBalance","params":[1],"response":{"jsonrpc":"2.0","id":0,"result":"0x2a"}}]"#,
+        ).unwrap());
+        let mut recorder = RecordLayer::new(recording.clone()).layer(source);
+
+        let resp =
+            single(recorder.call(request(7, "eth_getBalance", 1)).now_or_never().unwrap().unwrap());
+        assert_eq!(resp.id, Id::Number(7));
+        assert_eq!(recording.len(), 1);
+
+        let mut replay =
+            ReplayTransport::new(Recording::from_json(&recording.to_json().unwrap()).unwrap());
+        let resp =
+            single(replay.call(request(9, "eth_getBalance", 1)).now_or_never().unwrap().unwrap());
+        assert_eq!(resp.id, Id::Number(9));
+        match resp.payload {
+            ResponsePayload::Success(result) => assert_eq!(result.get(), r#""0x2a""#),
+            ResponsePayload::Failure(err) => panic!("unexpected error: {err}"),
+        }
+        assert_eq!(replay.remaining(), 0);
+
+        // Calls are served once, and params must match.
+        assert!(replay.call(request(10, "eth_getBalance", 1)).now_or_never().unwrap().is_err());
+        assert!(replay.call(request(11, "eth_getBalance", 2)).now_or_never().unwrap().is_err());
+    }
+
+    #[test]
+    fn replay_canonical_params() {
+        let mut replay = ReplayTransport::new(Recording::from_json(
+            r#"[{"method":"eth_call","params":[{"to":"0x01","data":"0x"}],"response":{"jsonrpc":"2.0","id":0,"result":"0x"}}]"#,
+        ).unwrap());
+        let request: RequestPacket = Request::new(
+            "eth_call",
+            Id::Number(1),
+            [serde_json::json!({"data": "0x", "to": "0x01"})],
+        )
+        .serialize()
+        .unwrap()
+        .into();
+
+        assert!(replay.call(request).now_or_never().unwrap().is_ok());
+    }
+
+    #[test]
+    fn replay_canonical_numbers() {
+        let mut replay = ReplayTransport::new(Recording::from_json(
+            r#"[{"method":"eth_getBalance","params":["0xAbC","0x01",2.0,"12"],"response":{"jsonrpc":"2.0","id":0,"result":"0x"}}]"#,
+        ).unwrap());
+        let request = |params: serde_json::Value| -> RequestPacket {
+            Request::new("eth_getBalance", Id::Number(1), params).serialize().unwrap().into()
+        };
+
+        // Decimal strings are not quantities.
+        let err = request(serde_json::json!(["0xabc", "0x01", 2, "0xc"]));
+        assert!(replay.call(err).now_or_never().unwrap().is_err());
+        // Leading zeros are significant.
+        let err = request(serde_json::json!(["0x0abc", "0x01", 2, "12"]));
+        assert!(replay.call(err).now_or_never().unwrap().is_err());
+        let err = request(serde_json::json!(["0xabc", "0x1", 2, "12"]));
+        assert!(replay.call(err).now_or_never().unwrap().is_err());
+        let ok = request(serde_json::json!(["0xabc", "0x01", 2, "12"]));
+        assert!(replay.call(ok).now_or_never().unwrap().is_ok());
+    }
+
+    #[test]
+    fn failed_batch_consumes_nothing() {
+        let mut replay = ReplayTransport::new(Recording::from_json(
+            r#"[{"method":"eth_getBalance","params":[1],"response":{"jsonrpc":"2.0","id":0,"result":"0x2a"}}]"#,
+        ).unwrap());
+        let batch = |params: &[u64]| {
+            RequestPacket::Batch(
+                params
+                    .iter()
+                    .enumerate()
+                    .map(|(id, p)| {
+                        Request::new("eth_getBalance", Id::Number(id as u64), (*p,))
+                            .serialize()
+                            .unwrap()
+                    })
+                    .collect(),
+            )
+        };
+
+        // The same call is only served once within a batch.
+        assert!(replay.call(batch(&[1, 1])).now_or_never().unwrap().is_err());
+        assert!(replay.call(batch(&[1, 2])).now_or_never().unwrap().is_err());
+        assert_eq!(replay.remaining(), 1);
+        assert!(replay.call(batch(&[1])).now_or_never().unwrap().is_ok());
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn replace_to_reorg() {
+        let block = |hash: &str| {
+            format!(
+                r#"[{{"method":"eth_getBlockByNumber","params":[1],"response":{{"jsonrpc":"2.0","id":0,"result":{{"hash":"{hash}"}}}}}}]"#
+            )
+        };
+        let result = |replay: &mut ReplayTransport| match single(
+            replay.call(request(0, "eth_getBlockByNumber", 1)).now_or_never().unwrap().unwrap(),
+        )
+        .payload
+        {
+            ResponsePayload::Success(result) => result.get().to_string(),
+            ResponsePayload::Failure(err) => panic!("unexpected error: {err}"),
+        };
+
+        let mut replay = ReplayTransport::new(Recording::from_json(&block("0xa")).unwrap());
+        replay.extend(Recording::from_json(&block("0xa")).unwrap());
+        assert_eq!(result(&mut replay), r#"{"hash":"0xa"}"#);
+
+        // The chain reorgs before the block is queried again.
+        replay.replace(Recording::from_json(&block("0xb")).unwrap());
+        assert_eq!(result(&mut replay), r#"{"hash":"0xb"}"#);
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn injected_failures() {
+        let recording = Recording::from_json(
+            r#"[{"method":"eth_getBalance","params":[1],"response":{"jsonrpc":"2.0","id":0,"result":"0x2a"}}]"#,
+        )
+        .unwrap();
+        let replay = ReplayTransport::new(recording.clone());
+        replay.fail_nth(0, TransportErrorKind::backend_gone);
+        assert!(replay.clone().call(request(0, "eth_getBalance", 1)).await.is_err());
+        assert!(replay.clone().call(request(1, "eth_getBalance", 1)).await.is_ok());
+
+        // Rate limit errors are retried by the retry layer.
+        let replay = ReplayTransport::new(recording);
+        replay.fail_nth(0, || TransportErrorKind::http_error(429, "rate limited".into()));
+        replay.fail_nth(1, || TransportErrorKind::http_error(429, "rate limited".into()));
+        let mut retry = RetryBackoffLayer::new(2, 1, 1000).layer(replay.clone());
+        assert!(retry.call(request(0, "eth_getBalance", 1)).await.is_ok());
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn random_failures() {
+        let call = r#"{"method":"eth_getBalance","params":[1],"response":{"jsonrpc":"2.0","id":0,"result":"0x2a"}}"#;
+        let recording = Recording::from_json(&format!("[{}]", vec![call; 100].join(","))).unwrap();
+        let failures = |probability: f64, seed: u64| {
+            let mut replay = ReplayTransport::new(recording.clone());
+            replay.fail_with_probability(probability, seed, TransportErrorKind::backend_gone);
+            (0..100)
+                .map(|id| replay.call(request(id, "eth_getBalance", 1)).now_or_never().unwrap())
+                .map(|res| res.is_err())
+                .collect::<Vec<_>>()
+        };
+
+        let failed = failures(0.3, 42);
+        assert_eq!(failed, failures(0.3, 42));
+        assert_ne!(failed, failures(0.3, 43));
+        let count = failed.iter().filter(|&&failed| failed).count();
+        assert!((10..50).contains(&count), "{count} failures");
+
+        assert!(failures(0.0, 42).iter().all(|&failed| !failed));
+        assert!(failures(1.0, 42).iter().all(|&failed| failed));
+    }
+}